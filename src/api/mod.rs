@@ -1 +1,3 @@
+pub mod models;
+pub mod rate_limit;
 pub mod requests;
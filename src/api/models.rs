@@ -0,0 +1,616 @@
+use serde::{Deserialize, Serialize};
+
+// Response envelope of endpoints returning a single object
+#[derive(Deserialize, Debug)]
+pub struct Data<T> {
+    pub data: T,
+}
+
+// Response envelope of paginated list endpoints
+#[derive(Deserialize, Debug)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub meta: Meta,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Meta {
+    pub total: u64,
+    pub page: u64,
+    pub limit: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Agent {
+    pub symbol: String,
+    pub headquarters: String,
+    pub credits: i64,
+    pub starting_faction: String,
+    #[serde(default)]
+    pub ship_count: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Faction {
+    pub symbol: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub headquarters: Option<String>,
+    pub traits: Vec<FactionTrait>,
+    pub is_recruiting: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct FactionTrait {
+    pub symbol: String,
+    pub name: String,
+    pub description: String,
+}
+
+// Status of the game server, returned by the API root
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatus {
+    pub status: String,
+    pub version: String,
+    pub reset_date: String,
+    pub server_resets: ServerResets,
+    pub stats: ServerStats,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ServerResets {
+    pub next: String,
+    pub frequency: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ServerStats {
+    pub agents: u64,
+    pub ships: u64,
+    pub systems: u64,
+    pub waypoints: u64,
+}
+
+// Data returned when registering a new agent
+#[derive(Deserialize, Debug)]
+pub struct Registration {
+    pub agent: Agent,
+    pub contract: Contract,
+    pub faction: Faction,
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Contract {
+    pub id: String,
+    pub faction_symbol: String,
+    #[serde(rename = "type")]
+    pub contract_type: String,
+    pub terms: ContractTerms,
+    pub accepted: bool,
+    pub fulfilled: bool,
+    #[serde(default)]
+    pub deadline_to_accept: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContractTerms {
+    pub deadline: String,
+    pub payment: ContractPayment,
+    #[serde(default)]
+    pub deliver: Vec<ContractDeliverGood>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractPayment {
+    pub on_accepted: i64,
+    pub on_fulfilled: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractDeliverGood {
+    pub trade_symbol: String,
+    pub destination_symbol: String,
+    pub units_required: u32,
+    pub units_fulfilled: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct System {
+    pub symbol: String,
+    pub sector_symbol: String,
+    #[serde(rename = "type")]
+    pub system_type: String,
+    pub x: i64,
+    pub y: i64,
+    // Only set on systems found by a scan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Waypoint {
+    pub symbol: String,
+    #[serde(rename = "type")]
+    pub waypoint_type: String,
+    pub system_symbol: String,
+    pub x: i64,
+    pub y: i64,
+    #[serde(default)]
+    pub orbitals: Vec<WaypointOrbital>,
+    pub traits: Vec<WaypointTrait>,
+    #[serde(default)]
+    pub faction: Option<WaypointFaction>,
+    #[serde(default)]
+    pub chart: Option<Chart>,
+    #[serde(default)]
+    pub is_under_construction: bool,
+}
+
+impl Waypoint {
+    pub fn has_trait(&self, trait_symbol: &str) -> bool {
+        // Check whether the waypoint has a trait, e.g., MARKETPLACE or SHIPYARD
+        self.traits.iter().any(|t| t.symbol == trait_symbol)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WaypointOrbital {
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WaypointTrait {
+    pub symbol: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WaypointFaction {
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Chart {
+    #[serde(default)]
+    pub submitted_by: Option<String>,
+    #[serde(default)]
+    pub submitted_on: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct JumpGate {
+    pub symbol: String,
+    // Empty while the jump gate is under construction
+    pub connections: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Construction {
+    pub symbol: String,
+    pub materials: Vec<ConstructionMaterial>,
+    pub is_complete: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConstructionMaterial {
+    pub trade_symbol: String,
+    pub required: u32,
+    pub fulfilled: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Market {
+    pub symbol: String,
+    pub exports: Vec<TradeGood>,
+    pub imports: Vec<TradeGood>,
+    pub exchange: Vec<TradeGood>,
+    // Only returned while one of the agent's ships is at the waypoint
+    #[serde(default)]
+    pub trade_goods: Option<Vec<MarketTradeGood>>,
+}
+
+impl Market {
+    pub fn trades(&self, trade_symbol: &str) -> bool {
+        // Check whether the market imports, exports or exchanges a good
+        self.exports
+            .iter()
+            .chain(&self.imports)
+            .chain(&self.exchange)
+            .any(|good| good.symbol == trade_symbol)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TradeGood {
+    pub symbol: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketTradeGood {
+    pub symbol: String,
+    pub trade_volume: u32,
+    pub supply: String,
+    pub purchase_price: i64,
+    pub sell_price: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Shipyard {
+    pub symbol: String,
+    pub ship_types: Vec<ShipyardShipType>,
+    // Only returned while one of the agent's ships is at the waypoint
+    #[serde(default)]
+    pub ships: Option<Vec<ShipyardShip>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipyardShipType {
+    #[serde(rename = "type")]
+    pub ship_type: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipyardShip {
+    #[serde(rename = "type")]
+    pub ship_type: String,
+    pub name: String,
+    pub purchase_price: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Ship {
+    pub symbol: String,
+    pub registration: ShipRegistration,
+    pub nav: ShipNav,
+    pub frame: ShipFrame,
+    pub reactor: ShipComponent,
+    pub engine: ShipComponent,
+    pub cooldown: Cooldown,
+    pub modules: Vec<ShipModule>,
+    pub mounts: Vec<ShipMount>,
+    pub cargo: ShipCargo,
+    pub fuel: ShipFuel,
+}
+
+impl Ship {
+    pub fn has_module(&self, module_symbol: &str) -> bool {
+        self.modules.iter().any(|m| m.symbol == module_symbol)
+    }
+
+    pub fn cargo_units(&self, trade_symbol: &str) -> u32 {
+        // Units of a good held in cargo, zero if there are none
+        self.cargo
+            .inventory
+            .iter()
+            .find(|item| item.symbol == trade_symbol)
+            .map(|item| item.units)
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipRegistration {
+    pub name: String,
+    pub faction_symbol: String,
+    pub role: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipNav {
+    pub system_symbol: String,
+    pub waypoint_symbol: String,
+    pub route: ShipNavRoute,
+    pub status: String,
+    pub flight_mode: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipNavRoute {
+    pub destination: ShipNavRouteWaypoint,
+    // Older API versions call the origin "departure"
+    #[serde(alias = "departure")]
+    pub origin: ShipNavRouteWaypoint,
+    pub departure_time: String,
+    pub arrival: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipNavRouteWaypoint {
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipFrame {
+    pub symbol: String,
+    pub module_slots: u32,
+    pub mounting_points: u32,
+    pub fuel_capacity: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipComponent {
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipModule {
+    pub symbol: String,
+    #[serde(default)]
+    pub capacity: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipMount {
+    pub symbol: String,
+    #[serde(default)]
+    pub strength: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipCargo {
+    pub capacity: u32,
+    pub units: u32,
+    pub inventory: Vec<ShipCargoItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipCargoItem {
+    pub symbol: String,
+    pub units: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipFuel {
+    pub current: u32,
+    pub capacity: u32,
+    #[serde(default)]
+    pub consumed: Option<ShipFuelConsumed>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipFuelConsumed {
+    pub amount: u32,
+    pub timestamp: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Cooldown {
+    pub ship_symbol: String,
+    pub total_seconds: u32,
+    pub remaining_seconds: u32,
+    #[serde(default)]
+    pub expiration: Option<String>,
+}
+
+// Ship of another agent as seen by a scan
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScannedShip {
+    pub symbol: String,
+    pub registration: ShipRegistration,
+    pub nav: ShipNav,
+    #[serde(default)]
+    pub frame: Option<ShipComponent>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketTransaction {
+    pub waypoint_symbol: String,
+    pub ship_symbol: String,
+    pub trade_symbol: String,
+    #[serde(rename = "type")]
+    pub transaction_type: String,
+    pub units: u32,
+    pub price_per_unit: i64,
+    pub total_price: i64,
+    pub timestamp: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShipyardTransaction {
+    pub price: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShipModificationTransaction {
+    pub total_price: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Extraction {
+    #[serde(rename = "yield")]
+    pub extracted: ExtractionYield,
+}
+
+// Surveys are stored locally and sent back to the API as they were received
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Survey {
+    pub signature: String,
+    pub symbol: String,
+    pub deposits: Vec<SurveyDeposit>,
+    pub expiration: String,
+    pub size: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SurveyDeposit {
+    pub symbol: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExtractionYield {
+    pub symbol: String,
+    pub units: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RefineGood {
+    pub trade_symbol: String,
+    pub units: u32,
+}
+
+// Data returned by ship interactions
+
+#[derive(Deserialize, Debug)]
+pub struct NavUpdate {
+    pub nav: ShipNav,
+}
+
+// Setting the flight mode returns the nav data either directly or nested under "nav"
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum FlightModeUpdate {
+    Nested(NavUpdate),
+    Nav(ShipNav),
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Navigation {
+    pub nav: ShipNav,
+    pub fuel: ShipFuel,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Refuel {
+    pub agent: Agent,
+    pub fuel: ShipFuel,
+    pub transaction: MarketTransaction,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ExtractionResult {
+    pub cooldown: Cooldown,
+    // Siphoning returns the same data under "siphon"
+    #[serde(alias = "siphon")]
+    pub extraction: Extraction,
+    pub cargo: ShipCargo,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SurveyResult {
+    pub cooldown: Cooldown,
+    pub surveys: Vec<Survey>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Trade {
+    pub agent: Agent,
+    pub cargo: ShipCargo,
+    pub transaction: MarketTransaction,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CargoUpdate {
+    pub cargo: ShipCargo,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SystemTravel {
+    pub nav: ShipNav,
+    // Jumps cost antimatter and credits, warps cost fuel
+    #[serde(default)]
+    pub cooldown: Option<Cooldown>,
+    #[serde(default)]
+    pub transaction: Option<MarketTransaction>,
+    #[serde(default)]
+    pub agent: Option<Agent>,
+    #[serde(default)]
+    pub fuel: Option<ShipFuel>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Refinement {
+    pub cargo: ShipCargo,
+    pub cooldown: Cooldown,
+    pub produced: Vec<RefineGood>,
+    pub consumed: Vec<RefineGood>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Modification {
+    pub agent: Agent,
+    pub transaction: ShipModificationTransaction,
+    pub cargo: ShipCargo,
+    // Module changes return the new modules, mount changes the new mounts
+    #[serde(default)]
+    pub modules: Option<Vec<ShipModule>>,
+    #[serde(default)]
+    pub mounts: Option<Vec<ShipMount>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SystemScan {
+    pub cooldown: Cooldown,
+    pub systems: Vec<System>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WaypointScan {
+    pub cooldown: Cooldown,
+    pub waypoints: Vec<Waypoint>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ShipScan {
+    pub cooldown: Cooldown,
+    pub ships: Vec<ScannedShip>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Charting {
+    pub chart: Chart,
+    pub waypoint: Waypoint,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ShipPurchase {
+    pub agent: Agent,
+    pub ship: Ship,
+    pub transaction: ShipyardTransaction,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Negotiation {
+    pub contract: Contract,
+}
+
+// Data returned by contract interactions, accepting and fulfilling also return the agent
+#[derive(Deserialize, Debug)]
+pub struct ContractUpdate {
+    pub contract: Contract,
+    #[serde(default)]
+    pub agent: Option<Agent>,
+    #[serde(default)]
+    pub cargo: Option<ShipCargo>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ConstructionSupply {
+    pub construction: Construction,
+    pub cargo: ShipCargo,
+}
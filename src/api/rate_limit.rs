@@ -0,0 +1,133 @@
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+// Token bucket shared by all requests of a TradersApi, safe to use from concurrent tasks
+pub struct RateLimiter {
+    // Tokens added per second
+    rate: f64,
+    // Maximum number of tokens, i.e., the burst size
+    capacity: f64,
+    bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+    // Set after a 429 response, no request is sent before this instant
+    paused_until: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(rate: f64, capacity: f64) -> RateLimiter {
+        RateLimiter {
+            rate,
+            capacity,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                last_refill: Instant::now(),
+                paused_until: None,
+            }),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        // Wait until a token is available and take it
+        while let Some(wait) = self.try_take() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn try_take(&self) -> Option<Duration> {
+        // Take a token, or return how long to wait for the next one
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        if let Some(paused_until) = bucket.paused_until {
+            if paused_until > now {
+                return Some(paused_until - now);
+            }
+            bucket.paused_until = None;
+        }
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+        bucket.last_refill = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        }
+    }
+
+    pub fn pause_for(&self, wait: Duration) {
+        // Hold back all requests after the API answered with 429
+        let mut bucket = self.bucket.lock().unwrap();
+        let until = Instant::now() + wait;
+        bucket.paused_until = Some(bucket.paused_until.map_or(until, |p| p.max(until)));
+        bucket.tokens = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_allows_burst_then_waits_for_refill() {
+        let limiter = RateLimiter::new(2.0, 2.0);
+        let start = Instant::now();
+
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn pause_for_holds_back_requests() {
+        let limiter = RateLimiter::new(2.0, 2.0);
+        let start = Instant::now();
+
+        limiter.pause_for(Duration::from_secs(3));
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(3));
+
+        // A shorter pause does not cut an active one short
+        limiter.pause_for(Duration::from_secs(5));
+        limiter.pause_for(Duration::from_secs(1));
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(8));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn acquire_is_shared_across_tasks() {
+        let limiter = Arc::new(RateLimiter::new(2.0, 2.0));
+        let start = Instant::now();
+
+        // Six tasks compete for tokens: two at once, then one every 500ms
+        let tasks: Vec<_> = (0..6)
+            .map(|_| {
+                let limiter = Arc::clone(&limiter);
+                tokio::spawn(async move {
+                    limiter.acquire().await;
+                    start.elapsed()
+                })
+            })
+            .collect();
+        let mut elapsed = Vec::new();
+        for task in tasks {
+            elapsed.push(task.await.unwrap());
+        }
+        elapsed.sort();
+
+        let expected: Vec<Duration> = [0, 0, 500, 1000, 1500, 2000]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+        assert_eq!(elapsed, expected);
+    }
+}
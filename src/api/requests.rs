@@ -1,27 +1,116 @@
-use reqwest::{Client, StatusCode};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use futures::stream::{self, Stream, TryStreamExt};
+use rand::Rng;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::api::models::{
+    Agent, Construction, ConstructionSupply, Contract, ContractUpdate, Data, Faction, JumpGate,
+    Market, Meta, Page, Registration, ServerStatus, Ship, ShipPurchase, Shipyard, System, Waypoint,
+};
+use crate::api::rate_limit::RateLimiter;
+use crate::error::Error;
+use crate::utils::status;
+
+// Maximum number of items the API returns per page
+pub const MAX_PAGE_LIMIT: u32 = 20;
+
+// User agent sent with every request
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+// Request budget of the API: 2 requests per second, sent one at a time at full rate
+const RATE_LIMIT_PER_SECOND: f64 = 2.0;
+const RATE_LIMIT_BURST: f64 = 2.0;
+
+// Number of times a request is sent again after the API answered with 429
+const RATE_LIMIT_RETRIES: u32 = 5;
+
+// Backoff before resending a request after a transient failure, doubled per attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
+
+// Page selection for list endpoints: a single page or all items
+pub enum Paging {
+    Page { page: u32, limit: u32 },
+    All,
+}
 
 pub struct TradersApi {
+    client: Client,
+    rate_limiter: RateLimiter,
+    timeout: Duration,
+    max_attempts: u32,
     api_url_root: String,
     api_suburl_register: String,
     api_suburl_status: String,
     api_suburl_location: String,
     api_suburl_contracts: String,
+    api_suburl_ships: String,
+    api_suburl_factions: String,
 }
 
-pub fn get_traders_api() -> TradersApi {
+pub fn get_traders_api(
+    game_status: &HashMap<String, String>,
+    timeout: Duration,
+    max_attempts: u32,
+) -> Result<TradersApi, Error> {
     // Initialize TradersApi struct with default values
-    TradersApi {
+    Ok(TradersApi {
+        client: build_client(game_status, timeout)?,
+        rate_limiter: RateLimiter::new(RATE_LIMIT_PER_SECOND, RATE_LIMIT_BURST),
+        timeout,
+        max_attempts,
         api_url_root: "https://api.spacetraders.io/v2/".to_string(),
         api_suburl_register: "register/".to_string(),
         api_suburl_status: "my/agent/".to_string(),
         api_suburl_location: "systems/".to_string(),
         api_suburl_contracts: "my/contracts/".to_string(),
+        api_suburl_ships: "my/ships/".to_string(),
+        api_suburl_factions: "factions/".to_string(),
+    })
+}
+
+fn build_client(game_status: &HashMap<String, String>, timeout: Duration) -> Result<Client, Error> {
+    // Default headers for every request, authorization only once an agent is logged in
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
+    if status::check_local_token(game_status) {
+        let mut auth_value =
+            HeaderValue::from_str(&format!("Bearer {}", game_status.get("token").unwrap()))
+                .map_err(|_| Error::LocalState("Saved token is not a valid header.".to_string()))?;
+        auth_value.set_sensitive(true);
+        headers.insert(header::AUTHORIZATION, auth_value);
     }
+
+    // One client for all requests, so connections are pooled and reused
+    Ok(Client::builder()
+        .user_agent(USER_AGENT)
+        .default_headers(headers)
+        .timeout(timeout)
+        .build()?)
 }
 
 impl TradersApi {
+    // Rebuild the client after the token changed, keeping the shared rate limiter
+    pub fn with_game_status(
+        self,
+        game_status: &HashMap<String, String>,
+    ) -> Result<TradersApi, Error> {
+        Ok(TradersApi {
+            client: build_client(game_status, self.timeout)?,
+            ..self
+        })
+    }
+
     // Immutable access to api_url_root via getter
     pub fn api_url_root(&self) -> &str {
         &self.api_url_root
@@ -47,93 +136,293 @@ impl TradersApi {
         &self.api_suburl_contracts
     }
 
-    // Response checker: check response status and returns appropriate data or error
-    pub async fn check_response(
+    // Immutable access to api_suburl_ships via getter
+    pub fn api_suburl_ships(&self) -> &str {
+        &self.api_suburl_ships
+    }
+
+    // Immutable access to api_suburl_factions via getter
+    pub fn api_suburl_factions(&self) -> &str {
+        &self.api_suburl_factions
+    }
+
+    // Request sender: send every request through the rate limiter, waiting and resending on 429
+    // and on transient failures, i.e., network errors, timeouts and 502/503/504
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, Error> {
+        let request = request.build()?;
+        // POST requests (purchase, sell, ...) are only resent if they were never sent
+        let idempotent = request.method() != Method::POST;
+        let mut rate_limit_retries = 0;
+        let mut attempt = 1;
+        loop {
+            // Requests with a streamed body cannot be resent
+            let Some(attempt_request) = request.try_clone() else {
+                self.rate_limiter.acquire().await;
+                return Ok(self.client.execute(request).await?);
+            };
+            self.rate_limiter.acquire().await;
+            let result = self.client.execute(attempt_request).await;
+
+            let failure = match &result {
+                Ok(resp)
+                    if resp.status() == StatusCode::TOO_MANY_REQUESTS
+                        && rate_limit_retries < RATE_LIMIT_RETRIES =>
+                {
+                    // Hold back all requests until the limit is reset
+                    let wait = rate_limit_wait(resp.headers());
+                    println!(
+                        "{} waiting {:.1}s...",
+                        "Rate limit reached,".yellow(),
+                        wait.as_secs_f64()
+                    );
+                    self.rate_limiter.pause_for(wait);
+                    rate_limit_retries += 1;
+                    continue;
+                }
+                Ok(resp) => retry_status_reason(resp.status(), idempotent),
+                Err(err) => retry_error_reason(err, idempotent),
+            };
+            let Some(failure) = failure else {
+                return result.map_err(Error::from);
+            };
+            if attempt >= self.max_attempts {
+                return result.map_err(Error::from);
+            }
+
+            let wait = retry_backoff(attempt);
+            attempt += 1;
+            println!(
+                "{} retrying in {:.1}s (attempt {} of {})...",
+                format!("Request failed ({}),", failure).yellow(),
+                wait.as_secs_f64(),
+                attempt,
+                self.max_attempts
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    // Response checker: check response status and deserialize data or return error
+    pub async fn check_response<T: DeserializeOwned>(
         &self,
         response: reqwest::Response,
         error_msg: &str,
-    ) -> Result<Value, Box<dyn std::error::Error>> {
+    ) -> Result<T, Error> {
         // Get response status
         let resp_status = response.status();
 
         // Get response text
         let resp_text = response.text().await?;
 
-        // Deserialize response text
-        let resp_value: Value = serde_json::from_str(&resp_text)?;
-
-        // check response
+        // check response and deserialize response text
         match resp_status {
-            StatusCode::OK => Ok(resp_value),
-            StatusCode::CREATED => Ok(resp_value),
-            _ => Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!(
-                    "{} - {}",
-                    error_msg,
-                    resp_value["error"]["message"]
-                        .to_string()
-                        .replace("\\\"", "")
-                ),
-            ))),
+            StatusCode::OK | StatusCode::CREATED => {
+                serde_json::from_str(&resp_text).map_err(|err| Error::Deserialize {
+                    context: error_msg.to_string(),
+                    message: err.to_string(),
+                })
+            }
+            _ => Err(Error::from_response(error_msg, resp_status, &resp_text)),
         }
     }
 }
 
+fn retry_status_reason(status: StatusCode, idempotent: bool) -> Option<String> {
+    // Gateway errors usually pass once the server recovers, but the request may have been processed
+    let transient = matches!(
+        status,
+        StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+    );
+    (idempotent && transient).then(|| status.to_string())
+}
+
+fn retry_error_reason(err: &reqwest::Error, idempotent: bool) -> Option<String> {
+    // Connection errors happen before anything is sent, so every request can be resent
+    let reason = if err.is_connect() {
+        "connection failed"
+    } else if idempotent && err.is_timeout() {
+        "timed out"
+    } else if idempotent && err.is_request() {
+        "network error"
+    } else {
+        return None;
+    };
+    Some(reason.to_string())
+}
+
+fn retry_backoff(attempt: u32) -> Duration {
+    // Exponential backoff with jitter: a random wait between half and the full delay
+    let delay = RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(attempt - 1))
+        .min(RETRY_MAX_DELAY);
+    delay.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+}
+
+fn rate_limit_wait(headers: &HeaderMap) -> Duration {
+    // Wait time of a 429 response from the retry-after or the x-ratelimit-reset header
+    let header_str = |name: &str| headers.get(name)?.to_str().ok();
+    if let Some(secs) = header_str("retry-after").and_then(|s| s.parse::<f64>().ok()) {
+        return Duration::from_secs_f64(secs.max(0.0));
+    }
+    header_str("x-ratelimit-reset")
+        .and_then(|reset| DateTime::parse_from_rfc3339(reset).ok())
+        .and_then(|reset| (reset.with_timezone(&Utc) - Utc::now()).to_std().ok())
+        .unwrap_or(Duration::from_secs(1))
+}
+
 impl TradersApi {
-    pub async fn remote_status_req(
+    // Page requester: get a single page of a list endpoint
+    async fn page_req<T: DeserializeOwned>(
         &self,
-        game_status: &HashMap<String, String>,
-    ) -> Result<Value, Box<dyn std::error::Error>> {
+        list_url: &str,
+        page: u32,
+        limit: u32,
+        error_msg: &str,
+    ) -> Result<Page<T>, Error> {
+        // Build request, public lists work without a token
+        let req = self
+            .client
+            .get(list_url)
+            .query(&[("page", page), ("limit", limit)]);
+
+        // Send request and check response
+        let resp = self.send(req).await?;
+        self.check_response(resp, error_msg).await
+    }
+
+    // List streamer: yield all items of a list endpoint, following pages until meta.total is reached
+    pub fn list_stream<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        list_url: String,
+        error_msg: &'a str,
+    ) -> impl Stream<Item = Result<T, Error>> + 'a {
+        stream::try_unfold((1, 0, false), move |(page, fetched, done)| {
+            let list_url = list_url.clone();
+            async move {
+                if done {
+                    return Ok::<_, Error>(None);
+                }
+                let resp: Page<T> = self
+                    .page_req(&list_url, page, MAX_PAGE_LIMIT, error_msg)
+                    .await?;
+
+                // Stop after the last page or on an empty page
+                let fetched = fetched + resp.data.len() as u64;
+                let done = resp.data.is_empty() || fetched >= resp.meta.total;
+
+                let page_items = stream::iter(resp.data.into_iter().map(Ok));
+                Ok(Some((page_items, (page + 1, fetched, done))))
+            }
+        })
+        .try_flatten()
+    }
+
+    // List requester: get a single page or all items of a list endpoint
+    pub async fn list_req<T: DeserializeOwned>(
+        &self,
+        list_url: String,
+        paging: &Paging,
+        error_msg: &str,
+    ) -> Result<Page<T>, Error> {
+        match paging {
+            Paging::Page { page, limit } => {
+                self.page_req(&list_url, *page, *limit, error_msg).await
+            }
+            Paging::All => {
+                // Collect all items into a single response shaped like a page
+                let items: Vec<T> = self.list_stream(list_url, error_msg).try_collect().await?;
+                let total = items.len() as u64;
+                Ok(Page {
+                    data: items,
+                    meta: Meta {
+                        total,
+                        page: 1,
+                        limit: total,
+                    },
+                })
+            }
+        }
+    }
+
+    pub async fn server_status_req(&self) -> Result<ServerStatus, Error> {
+        // Send request to the API root, which needs no token
+        let resp = self.send(self.client.get(self.api_url_root())).await?;
+
+        // Check response
+        self.check_response(resp, "Error getting server status")
+            .await
+    }
+
+    pub async fn remote_status_req(&self) -> Result<Agent, Error> {
         // Build the URL
         let url = format!("{}{}", self.api_url_root(), self.api_suburl_status());
 
-        // Initialize client and send request
-        let client: Client = reqwest::Client::new();
-        let resp = client
-            .get(url)
-            .header("Content-Type", "application/json")
-            .header(
-                "Authorization",
-                "Bearer ".to_owned() + game_status.get("token").unwrap(),
-            )
-            .send()
-            .await?;
+        // Send request
+        let resp = self.send(self.client.get(url)).await?;
 
         // check response
-        self.check_response(resp, "Error getting remote status")
+        self.check_response::<Data<Agent>>(resp, "Error getting remote status")
             .await
+            .map(|resp| resp.data)
     }
 
-    pub async fn reg_agent_req(&self, callsign: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    pub async fn reg_agent_req(
+        &self,
+        callsign: &str,
+        faction: &str,
+        email: Option<&String>,
+    ) -> Result<Registration, Error> {
         // Build url
         let url = format!("{}{}", self.api_url_root(), self.api_suburl_register());
 
-        // Build request body with callsign and faction
+        // Build request body with callsign, faction and optional email
         let mut map = HashMap::new();
         map.insert("symbol", callsign);
-        map.insert("faction", "COSMIC");
-
-        // Initialize client and send request
-        let client: Client = reqwest::Client::new();
-        let resp = client
-            .post(url)
-            .header("Content-Type", "application/json")
-            .header("Accept", "application/json")
-            .json(&map)
-            .send()
-            .await?;
+        map.insert("faction", faction);
+        if let Some(email) = email {
+            map.insert("email", email);
+        }
+
+        // Send request
+        let resp = self.send(self.client.post(url).json(&map)).await?;
 
         // check response
-        self.check_response(resp, "Error registering new agent")
+        self.check_response::<Data<Registration>>(resp, "Error registering new agent")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn faction_data_req(&self, faction_symbol: &str) -> Result<Faction, Error> {
+        // Build url
+        let url = format!(
+            "{}{}{}",
+            self.api_url_root(),
+            self.api_suburl_factions(),
+            faction_symbol
+        );
+
+        // Send request, factions are public and need no token
+        let resp = self.send(self.client.get(url)).await?;
+
+        // Check response
+        self.check_response::<Data<Faction>>(resp, "Error getting faction data")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn faction_list_req(&self, paging: &Paging) -> Result<Page<Faction>, Error> {
+        // Build url
+        let url = format!("{}{}", self.api_url_root(), self.api_suburl_factions());
+
+        // Get factions page by page
+        self.list_req(url, paging, "Error getting faction data")
             .await
     }
 
     pub async fn loc_waypoint_req(
         &self,
-        game_status: &HashMap<String, String>,
         sys_waypoint_tup: (String, String),
-    ) -> Result<Value, Box<dyn std::error::Error>> {
+    ) -> Result<Waypoint, Error> {
         // Build url
         let url = format!(
             "{}{}{}/waypoints/{}",
@@ -143,125 +432,518 @@ impl TradersApi {
             sys_waypoint_tup.1
         );
 
-        // Initialize client and send request
-        let client: Client = reqwest::Client::new();
-        let resp = client
-            .get(url)
-            .header("Content-Type", "application/json")
-            .header(
-                "Authorization",
-                "Bearer ".to_owned() + game_status.get("token").unwrap(),
-            )
-            .send()
-            .await?;
+        // Send request
+        let resp = self.send(self.client.get(url)).await?;
 
         // Check response
-        self.check_response(resp, "Error getting waypoint data")
+        self.check_response::<Data<Waypoint>>(resp, "Error getting waypoint data")
             .await
+            .map(|resp| resp.data)
     }
 
     pub async fn loc_system_req(
         &self,
-        game_status: &HashMap<String, String>,
         sys_name: &str,
-    ) -> Result<Value, Box<dyn std::error::Error>> {
+        filters: &[(&str, String)],
+        paging: &Paging,
+    ) -> Result<Page<Waypoint>, Error> {
+        // Build url with waypoint type and trait filters as query parameters
+        let url = Url::parse_with_params(
+            &format!(
+                "{}{}{}/waypoints",
+                self.api_url_root(),
+                self.api_suburl_location(),
+                sys_name
+            ),
+            filters,
+        )
+        .map_err(|_| Error::Command(format!("Invalid system symbol '{}'", sys_name)))?
+        .to_string();
+
+        // Get system waypoints page by page
+        self.list_req(url, paging, "Error getting system data")
+            .await
+    }
+
+    pub async fn galaxy_systems_req(&self, paging: &Paging) -> Result<Page<System>, Error> {
+        // Build url
+        let url = format!("{}{}", self.api_url_root(), self.api_suburl_location());
+
+        // Get systems page by page
+        self.list_req(url, paging, "Error getting systems data")
+            .await
+    }
+
+    pub async fn loc_jump_gate_req(
+        &self,
+        sys_waypoint_tup: (String, String),
+    ) -> Result<JumpGate, Error> {
         // Build url
         let url = format!(
-            "{}{}{}/waypoints",
+            "{}{}{}/waypoints/{}/jump-gate",
             self.api_url_root(),
             self.api_suburl_location(),
-            sys_name
+            sys_waypoint_tup.0,
+            sys_waypoint_tup.1
         );
 
-        // Initialize client and send request
-        let client: Client = reqwest::Client::new();
-        let resp = client
-            .get(url)
-            .header("Content-Type", "application/json")
-            .header(
-                "Authorization",
-                "Bearer ".to_owned() + game_status.get("token").unwrap(),
-            )
-            .send()
-            .await?;
+        // Send request
+        let resp = self.send(self.client.get(url)).await?;
 
         // Check response
-        self.check_response(resp, "Error getting system data").await
+        self.check_response::<Data<JumpGate>>(resp, "Error getting jump gate data")
+            .await
+            .map(|resp| resp.data)
     }
 
-    pub async fn contract_data_req(
+    pub async fn loc_construction_req(
         &self,
-        game_status: &HashMap<String, String>,
-        contract_id: Option<&String>,
-    ) -> Result<Value, Box<dyn std::error::Error>> {
+        sys_waypoint_tup: (String, String),
+    ) -> Result<Construction, Error> {
         // Build url
-        let url: String = match contract_id {
-            None => {
-                format!("{}{}", self.api_url_root(), self.api_suburl_contracts())
-            }
-            Some(id) => {
-                format!(
-                    "{}{}/{}",
-                    self.api_url_root(),
-                    self.api_suburl_contracts(),
-                    id
-                )
-            }
-        };
+        let url = format!(
+            "{}{}{}/waypoints/{}/construction",
+            self.api_url_root(),
+            self.api_suburl_location(),
+            sys_waypoint_tup.0,
+            sys_waypoint_tup.1
+        );
 
-        // Initialize client and send request
-        let client: Client = reqwest::Client::new();
-        let resp_text = client
-            .get(url)
-            .header("Content-Type", "application/json")
-            .header(
-                "Authorization",
-                "Bearer ".to_owned() + game_status.get("token").unwrap(),
-            )
-            .send()
-            .await?;
+        // Send request
+        let resp = self.send(self.client.get(url)).await?;
+
+        // Check response
+        self.check_response::<Data<Construction>>(resp, "Error getting construction data")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn construction_supply_req(
+        &self,
+        sys_waypoint_tup: (String, String),
+        request_body: Value,
+    ) -> Result<ConstructionSupply, Error> {
+        // Build url
+        let url = format!(
+            "{}{}{}/waypoints/{}/construction/supply",
+            self.api_url_root(),
+            self.api_suburl_location(),
+            sys_waypoint_tup.0,
+            sys_waypoint_tup.1
+        );
+
+        // Send request
+        let resp = self.send(self.client.post(url).json(&request_body)).await?;
+
+        // Check response
+        self.check_response::<Data<ConstructionSupply>>(resp, "Error supplying construction site")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn loc_market_req(
+        &self,
+        sys_waypoint_tup: (String, String),
+    ) -> Result<Market, Error> {
+        // Build url
+        let url = format!(
+            "{}{}{}/waypoints/{}/market",
+            self.api_url_root(),
+            self.api_suburl_location(),
+            sys_waypoint_tup.0,
+            sys_waypoint_tup.1
+        );
+
+        // Send request
+        let resp = self.send(self.client.get(url)).await?;
+
+        // Check response
+        self.check_response::<Data<Market>>(resp, "Error getting market data")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn loc_shipyard_req(
+        &self,
+        sys_waypoint_tup: (String, String),
+    ) -> Result<Shipyard, Error> {
+        // Build url
+        let url = format!(
+            "{}{}{}/waypoints/{}/shipyard",
+            self.api_url_root(),
+            self.api_suburl_location(),
+            sys_waypoint_tup.0,
+            sys_waypoint_tup.1
+        );
+
+        // Send request
+        let resp = self.send(self.client.get(url)).await?;
 
         // Check response
-        self.check_response(resp_text, "Error getting contract data")
+        self.check_response::<Data<Shipyard>>(resp, "Error getting shipyard data")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn contract_data_req(&self, contract_id: &str) -> Result<Contract, Error> {
+        // Build url
+        let url = format!(
+            "{}{}{}",
+            self.api_url_root(),
+            self.api_suburl_contracts(),
+            contract_id
+        );
+
+        // Send request
+        let resp = self.send(self.client.get(url)).await?;
+
+        // Check response
+        self.check_response::<Data<Contract>>(resp, "Error getting contract data")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn contract_list_req(&self, paging: &Paging) -> Result<Page<Contract>, Error> {
+        // Build url
+        let url = format!("{}{}", self.api_url_root(), self.api_suburl_contracts());
+
+        // Get contracts page by page
+        self.list_req(url, paging, "Error getting contract data")
             .await
     }
 
     pub async fn contract_interact_req(
         &self,
-        game_status: &HashMap<String, String>,
         contract_id: &str,
         interact_type: &str,
-        request_body: HashMap<&str, &str>,
-    ) -> Result<Value, Box<dyn std::error::Error>> {
+        request_body: Value,
+    ) -> Result<ContractUpdate, Error> {
         // Build url
         let base_url = format!(
-            "{}{}/{}",
+            "{}{}{}",
             self.api_url_root(),
             self.api_suburl_contracts(),
             contract_id
         );
         let url = match interact_type {
             "accept" => format!("{}/accept", base_url),
+            "deliver" => format!("{}/deliver", base_url),
             "fulfill" => format!("{}/fulfill", base_url),
-            _ => panic!("Invalid contract interaction type"),
+            _ => {
+                return Err(Error::Command(format!(
+                    "Invalid contract interaction type '{}'",
+                    interact_type
+                )))
+            }
         };
 
-        // Initialize client and send request
-        let client: Client = reqwest::Client::new();
-        let resp_text = client
-            .post(url)
-            .header("Content-Type", "application/json")
-            .header(
-                "Authorization",
-                "Bearer ".to_owned() + game_status.get("token").unwrap(),
-            )
-            .header("Accept", "application/json")
-            .json(&request_body)
-            .send()
+        // Send request
+        let resp = self.send(self.client.post(url).json(&request_body)).await?;
+
+        // Check response
+        self.check_response::<Data<ContractUpdate>>(resp, "Error interacting with contract")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn ship_data_req(&self, ship_symbol: &str) -> Result<Ship, Error> {
+        // Build url
+        let url = format!(
+            "{}{}{}",
+            self.api_url_root(),
+            self.api_suburl_ships(),
+            ship_symbol
+        );
+
+        // Send request
+        let resp = self.send(self.client.get(url)).await?;
+
+        // Check response
+        self.check_response::<Data<Ship>>(resp, "Error getting ship data")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn ship_list_req(&self, paging: &Paging) -> Result<Page<Ship>, Error> {
+        // Build url
+        let url = format!("{}{}", self.api_url_root(), self.api_suburl_ships());
+
+        // Get ships page by page
+        self.list_req(url, paging, "Error getting ship data").await
+    }
+
+    pub async fn ship_purchase_req(
+        &self,
+        ship_type: &str,
+        waypoint: &str,
+    ) -> Result<ShipPurchase, Error> {
+        // Build url
+        let url = format!("{}{}", self.api_url_root(), self.api_suburl_ships());
+
+        // Build request body with ship type and shipyard waypoint
+        let mut map = HashMap::new();
+        map.insert("shipType", ship_type);
+        map.insert("waypointSymbol", waypoint);
+
+        // Send request
+        let resp = self.send(self.client.post(url).json(&map)).await?;
+
+        // Check response
+        self.check_response::<Data<ShipPurchase>>(resp, "Error purchasing ship")
+            .await
+            .map(|resp| resp.data)
+    }
+
+    pub async fn ship_interact_req<T: DeserializeOwned>(
+        &self,
+        ship_symbol: &str,
+        interact_type: &str,
+        request_body: Value,
+    ) -> Result<T, Error> {
+        // Build url and pick http method for the interaction
+        let base_url = format!(
+            "{}{}{}",
+            self.api_url_root(),
+            self.api_suburl_ships(),
+            ship_symbol
+        );
+        let (method, url) = match interact_type {
+            "orbit" => (Method::POST, format!("{}/orbit", base_url)),
+            "dock" => (Method::POST, format!("{}/dock", base_url)),
+            "navigate" => (Method::POST, format!("{}/navigate", base_url)),
+            "flight_mode" => (Method::PATCH, format!("{}/nav", base_url)),
+            "refuel" => (Method::POST, format!("{}/refuel", base_url)),
+            "extract" => (Method::POST, format!("{}/extract", base_url)),
+            "extract_survey" => (Method::POST, format!("{}/extract/survey", base_url)),
+            "survey" => (Method::POST, format!("{}/survey", base_url)),
+            "siphon" => (Method::POST, format!("{}/siphon", base_url)),
+            "sell" => (Method::POST, format!("{}/sell", base_url)),
+            "purchase" => (Method::POST, format!("{}/purchase", base_url)),
+            "jettison" => (Method::POST, format!("{}/jettison", base_url)),
+            "transfer" => (Method::POST, format!("{}/transfer", base_url)),
+            "negotiate" => (Method::POST, format!("{}/negotiate/contract", base_url)),
+            "jump" => (Method::POST, format!("{}/jump", base_url)),
+            "warp" => (Method::POST, format!("{}/warp", base_url)),
+            "install_module" => (Method::POST, format!("{}/modules/install", base_url)),
+            "remove_module" => (Method::POST, format!("{}/modules/remove", base_url)),
+            "install_mount" => (Method::POST, format!("{}/mounts/install", base_url)),
+            "remove_mount" => (Method::POST, format!("{}/mounts/remove", base_url)),
+            "scan_systems" => (Method::POST, format!("{}/scan/systems", base_url)),
+            "scan_waypoints" => (Method::POST, format!("{}/scan/waypoints", base_url)),
+            "scan_ships" => (Method::POST, format!("{}/scan/ships", base_url)),
+            "chart" => (Method::POST, format!("{}/chart", base_url)),
+            "refine" => (Method::POST, format!("{}/refine", base_url)),
+            _ => {
+                return Err(Error::Command(format!(
+                    "Invalid ship interaction type '{}'",
+                    interact_type
+                )))
+            }
+        };
+
+        // Send request
+        let resp = self
+            .send(self.client.request(method, url).json(&request_body))
             .await?;
 
         // Check response
-        self.check_response(resp_text, "Error interacting with contract")
+        self.check_response::<Data<T>>(resp, "Error interacting with ship")
             .await
+            .map(|resp| resp.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration as ChronoDuration;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    async fn serve(response: &'static str) -> (String, Arc<AtomicUsize>) {
+        // Local server answering every request with the same raw response, counting requests
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = [0; 4096];
+                let _ = stream.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (url, requests)
+    }
+
+    async fn closed_port_url() -> String {
+        // Port that was just free again, so connecting to it is refused
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}/", listener.local_addr().unwrap())
+    }
+
+    fn test_api(max_attempts: u32) -> TradersApi {
+        get_traders_api(&HashMap::new(), Duration::from_secs(5), max_attempts).unwrap()
+    }
+
+    #[test]
+    fn retry_backoff_doubles_with_jitter_up_to_max() {
+        for _ in 0..100 {
+            let first = retry_backoff(1);
+            assert!(first >= Duration::from_millis(250) && first <= Duration::from_millis(500));
+            let third = retry_backoff(3);
+            assert!(third >= Duration::from_secs(1) && third <= Duration::from_secs(2));
+            let capped = retry_backoff(u32::MAX);
+            assert!(capped >= RETRY_MAX_DELAY / 2 && capped <= RETRY_MAX_DELAY);
+        }
+    }
+
+    #[test]
+    fn retry_status_reason_only_retries_idempotent_gateway_errors() {
+        for status in [
+            StatusCode::BAD_GATEWAY,
+            StatusCode::SERVICE_UNAVAILABLE,
+            StatusCode::GATEWAY_TIMEOUT,
+        ] {
+            assert_eq!(retry_status_reason(status, true), Some(status.to_string()));
+            assert_eq!(retry_status_reason(status, false), None);
+        }
+        for status in [
+            StatusCode::OK,
+            StatusCode::BAD_REQUEST,
+            StatusCode::NOT_FOUND,
+            StatusCode::INTERNAL_SERVER_ERROR,
+        ] {
+            assert_eq!(retry_status_reason(status, true), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn retry_error_reason_retries_refused_connections_for_all_methods() {
+        let err = Client::new()
+            .post(closed_port_url().await)
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.is_connect());
+        assert_eq!(
+            retry_error_reason(&err, false).as_deref(),
+            Some("connection failed")
+        );
+        assert_eq!(
+            retry_error_reason(&err, true).as_deref(),
+            Some("connection failed")
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_error_reason_only_retries_idempotent_timeouts() {
+        // Server accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let err = Client::new()
+            .get(url)
+            .timeout(Duration::from_millis(50))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout());
+        assert_eq!(retry_error_reason(&err, true).as_deref(), Some("timed out"));
+        assert_eq!(retry_error_reason(&err, false), None);
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn retry_error_reason_only_retries_idempotent_requests_after_sending() {
+        // Server reads the request and closes the connection without answering
+        let (url, _) = serve("").await;
+        let err = Client::new().get(url).send().await.unwrap_err();
+        assert!(!err.is_connect());
+        assert_eq!(
+            retry_error_reason(&err, true).as_deref(),
+            Some("network error")
+        );
+        assert_eq!(retry_error_reason(&err, false), None);
+    }
+
+    #[tokio::test]
+    async fn send_retries_get_up_to_max_attempts_on_503() {
+        let (url, requests) =
+            serve("HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n").await;
+        let api = test_api(3);
+        let resp = api.send(api.client.get(url)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn send_does_not_resend_post_on_503() {
+        let (url, requests) =
+            serve("HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n").await;
+        let api = test_api(3);
+        let resp = api.send(api.client.post(url)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn send_resends_post_when_connection_failed() {
+        // The second attempt only starts after the backoff of the first
+        let api = test_api(2);
+        let start = std::time::Instant::now();
+        let err = api
+            .send(api.client.post(closed_port_url().await))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Transport(err) if err.is_connect()));
+        assert!(start.elapsed() >= RETRY_BASE_DELAY / 2);
+    }
+
+    fn headers(pairs: &[(&'static str, String)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn rate_limit_wait_uses_retry_after_seconds() {
+        let headers = headers(&[("retry-after", "2.5".to_string())]);
+        assert_eq!(rate_limit_wait(&headers), Duration::from_millis(2500));
+    }
+
+    #[test]
+    fn rate_limit_wait_prefers_retry_after_over_reset() {
+        let reset = (Utc::now() + ChronoDuration::seconds(30)).to_rfc3339();
+        let headers = headers(&[
+            ("retry-after", "1".to_string()),
+            ("x-ratelimit-reset", reset),
+        ]);
+        assert_eq!(rate_limit_wait(&headers), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn rate_limit_wait_uses_reset_timestamp() {
+        let reset = (Utc::now() + ChronoDuration::seconds(3)).to_rfc3339();
+        let wait = rate_limit_wait(&headers(&[("x-ratelimit-reset", reset)]));
+        assert!(wait > Duration::from_secs(2) && wait <= Duration::from_secs(3));
+    }
+
+    #[test]
+    fn rate_limit_wait_falls_back_to_one_second() {
+        // No headers, an unparsable header or a reset in the past
+        let past_reset = (Utc::now() - ChronoDuration::seconds(3)).to_rfc3339();
+        for headers in [
+            HeaderMap::new(),
+            headers(&[("retry-after", "soon".to_string())]),
+            headers(&[("x-ratelimit-reset", past_reset)]),
+        ] {
+            assert_eq!(rate_limit_wait(&headers), Duration::from_secs(1));
+        }
     }
 }
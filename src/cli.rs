@@ -1,48 +1,207 @@
-use clap::{Arg, ArgAction, Command};
+use clap::{value_parser, Arg, ArgAction, ArgGroup, Command};
 
 // define the command strings with a static str for each command
 // and a tuple for each Argument: (name/long, id, short)
 pub struct CommandStrings {
     // subcommands
+    pub sc_cargo: &'static str,
     pub sc_contract: &'static str,
+    pub sc_faction: &'static str,
+    pub sc_galaxy: &'static str,
+    pub sc_loadout: &'static str,
     pub sc_location: &'static str,
     pub sc_login: &'static str,
+    pub sc_market: &'static str,
     pub sc_new: &'static str,
+    pub sc_scan: &'static str,
+    pub sc_ship: &'static str,
+    pub sc_shipyard: &'static str,
     pub sc_status: &'static str,
     // Args
     pub arg_accept: (&'static str, &'static str, char),
+    pub arg_all: (&'static str, &'static str, char),
     pub arg_callsign: (&'static str, &'static str, char),
+    pub arg_chart: (&'static str, &'static str, char),
+    pub arg_deliver: (&'static str, &'static str, char),
+    pub arg_dock: (&'static str, &'static str, char),
+    pub arg_email: (&'static str, &'static str, char),
+    pub arg_extract: (&'static str, &'static str, char),
+    pub arg_faction: (&'static str, &'static str, char),
+    pub arg_flight_mode: (&'static str, &'static str, char),
+    pub arg_from_cargo: (&'static str, &'static str, char),
     pub arg_fulfill: (&'static str, &'static str, char),
     pub arg_id: (&'static str, &'static str, char),
+    pub arg_install_module: (&'static str, &'static str, char),
+    pub arg_install_mount: (&'static str, &'static str, char),
+    pub arg_jettison: (&'static str, &'static str, char),
+    pub arg_jump: (&'static str, &'static str, char),
+    pub arg_jump_gate: (&'static str, &'static str, char),
+    pub arg_known: (&'static str, &'static str, char),
+    pub arg_limit: (&'static str, &'static str, char),
     pub arg_local: (&'static str, &'static str, char),
+    pub arg_max_attempts: (&'static str, &'static str, char),
+    pub arg_navigate: (&'static str, &'static str, char),
+    pub arg_negotiate: (&'static str, &'static str, char),
+    pub arg_offline: (&'static str, &'static str, char),
+    pub arg_orbit: (&'static str, &'static str, char),
+    pub arg_page: (&'static str, &'static str, char),
+    pub arg_purchase: (&'static str, &'static str, char),
+    pub arg_refine: (&'static str, &'static str, char),
+    pub arg_refuel: (&'static str, &'static str, char),
     pub arg_remote: (&'static str, &'static str, char),
+    pub arg_remove_module: (&'static str, &'static str, char),
+    pub arg_remove_mount: (&'static str, &'static str, char),
+    pub arg_sell: (&'static str, &'static str, char),
+    pub arg_server: (&'static str, &'static str, char),
+    pub arg_ships: (&'static str, &'static str, char),
+    pub arg_siphon: (&'static str, &'static str, char),
+    pub arg_supply: (&'static str, &'static str, char),
+    pub arg_survey: (&'static str, &'static str, char),
+    pub arg_surveys: (&'static str, &'static str, char),
+    pub arg_symbol: (&'static str, &'static str, char),
+    pub arg_sync: (&'static str, &'static str, char),
     pub arg_system: (&'static str, &'static str, char),
+    pub arg_systems: (&'static str, &'static str, char),
+    pub arg_timeout: (&'static str, &'static str, char),
+    pub arg_trait: (&'static str, &'static str, char),
+    pub arg_transfer: (&'static str, &'static str, char),
+    pub arg_type: (&'static str, &'static str, char),
+    pub arg_units: (&'static str, &'static str, char),
+    pub arg_wait: (&'static str, &'static str, char),
+    pub arg_wait_for: (&'static str, &'static str, char),
+    pub arg_warp: (&'static str, &'static str, char),
     pub arg_waypoint: (&'static str, &'static str, char),
+    pub arg_waypoints: (&'static str, &'static str, char),
+    pub arg_with_survey: (&'static str, &'static str, char),
 }
 
 pub static ALL_COMMANDS: CommandStrings = CommandStrings {
     // subcommands
+    sc_cargo: "cargo",
     sc_contract: "contract",
+    sc_faction: "faction",
+    sc_galaxy: "galaxy",
+    sc_loadout: "loadout",
     sc_location: "location",
     sc_login: "login",
+    sc_market: "market",
     sc_new: "new",
+    sc_scan: "scan",
+    sc_ship: "ship",
+    sc_shipyard: "shipyard",
     sc_status: "status",
     // Args
     arg_accept: ("accept", "id_accept", 'a'),
+    arg_all: ("all", "id_all", 'A'),
     arg_callsign: ("callsign", "id_callsign", 'c'),
+    arg_chart: ("chart", "id_chart", 'c'),
+    arg_deliver: ("deliver", "id_deliver", 'd'),
+    arg_dock: ("dock", "id_dock", 'd'),
+    arg_email: ("email", "id_email", 'e'),
+    arg_extract: ("extract", "id_extract", 'e'),
+    arg_faction: ("faction", "id_faction", 'f'),
+    arg_flight_mode: ("flight-mode", "id_flight_mode", 'f'),
+    arg_from_cargo: ("from-cargo", "id_from_cargo", 'c'),
     arg_fulfill: ("fulfill", "id_fulfill", 'f'),
     arg_id: ("id", "id_id", 'i'),
+    arg_install_module: ("install-module", "id_install_module", 'm'),
+    arg_install_mount: ("install-mount", "id_install_mount", 'M'),
+    arg_jettison: ("jettison", "id_jettison", 'j'),
+    arg_jump: ("jump", "id_jump", 'j'),
+    arg_jump_gate: ("jump-gate", "id_jump_gate", 'g'),
+    arg_known: ("known", "id_known", 'k'),
+    arg_limit: ("limit", "id_limit", 'L'),
     arg_local: ("local", "id_local", 'l'),
+    arg_max_attempts: ("max-attempts", "id_max_attempts", 'm'),
+    arg_navigate: ("navigate", "id_navigate", 'n'),
+    arg_negotiate: ("negotiate", "id_negotiate", 'n'),
+    arg_offline: ("offline", "id_offline", 'o'),
+    arg_orbit: ("orbit", "id_orbit", 'o'),
+    arg_page: ("page", "id_page", 'P'),
+    arg_purchase: ("purchase", "id_purchase", 'p'),
+    arg_refine: ("refine", "id_refine", 'x'),
+    arg_refuel: ("refuel", "id_refuel", 'r'),
     arg_remote: ("remote", "id_remote", 'r'),
+    arg_remove_module: ("remove-module", "id_remove_module", 'r'),
+    arg_remove_mount: ("remove-mount", "id_remove_mount", 'R'),
+    arg_sell: ("sell", "id_sell", 's'),
+    arg_server: ("server", "id_server", 's'),
+    arg_ships: ("ships", "id_ships", 'p'),
+    arg_siphon: ("siphon", "id_siphon", 'i'),
+    arg_supply: ("supply", "id_supply", 'u'),
+    arg_survey: ("survey", "id_survey", 'v'),
+    arg_surveys: ("surveys", "id_surveys", 'y'),
+    arg_symbol: ("symbol", "id_symbol", 's'),
+    arg_sync: ("sync", "id_sync", 'y'),
     arg_system: ("system", "id_system", 's'),
+    arg_systems: ("systems", "id_systems", 's'),
+    arg_timeout: ("timeout", "id_timeout", 't'),
+    arg_trait: ("trait", "id_trait", 'T'),
+    arg_transfer: ("transfer", "id_transfer", 't'),
+    arg_type: ("type", "id_type", 't'),
+    arg_units: ("units", "id_units", 'u'),
+    arg_wait: ("wait", "id_wait", 'a'),
+    arg_wait_for: ("wait-for", "id_wait_for", 'W'),
+    arg_warp: ("warp", "id_warp", 'p'),
     arg_waypoint: ("waypoint", "id_waypoint", 'w'),
+    arg_waypoints: ("waypoints", "id_waypoints", 'w'),
+    arg_with_survey: ("with-survey", "id_with_survey", 'w'),
 };
 
+// paging arguments shared by all commands listing items
+fn list_args() -> [Arg; 3] {
+    [
+        Arg::new(ALL_COMMANDS.arg_page.0)
+            .help("The page of the list to view, starting at 1.")
+            .id(ALL_COMMANDS.arg_page.1)
+            .short(ALL_COMMANDS.arg_page.2)
+            .long(ALL_COMMANDS.arg_page.0)
+            .value_parser(value_parser!(u32).range(1..))
+            .action(ArgAction::Set),
+        Arg::new(ALL_COMMANDS.arg_limit.0)
+            .help("The number of items per page, between 1 and 20.")
+            .id(ALL_COMMANDS.arg_limit.1)
+            .short(ALL_COMMANDS.arg_limit.2)
+            .long(ALL_COMMANDS.arg_limit.0)
+            .value_parser(value_parser!(u32).range(1..=20))
+            .action(ArgAction::Set),
+        Arg::new(ALL_COMMANDS.arg_all.0)
+            .help("To fetch all pages of the list.")
+            .id(ALL_COMMANDS.arg_all.1)
+            .short(ALL_COMMANDS.arg_all.2)
+            .long(ALL_COMMANDS.arg_all.0)
+            .action(ArgAction::SetTrue)
+            .conflicts_with_all([ALL_COMMANDS.arg_page.1, ALL_COMMANDS.arg_limit.1]),
+    ]
+}
+
 pub fn cli() -> Command {
     Command::new("rst")
             .about("A rust based SpaceTraders CLI.")
             .arg_required_else_help(true)
             .subcommand_required(true)
+            // request timeout for all subcommands
+            .arg(
+                Arg::new(ALL_COMMANDS.arg_timeout.0)
+                    .help("Timeout in seconds for each request to the SpaceTraders API.")
+                    .id(ALL_COMMANDS.arg_timeout.1)
+                    .short(ALL_COMMANDS.arg_timeout.2)
+                    .long(ALL_COMMANDS.arg_timeout.0)
+                    .value_name("SECONDS")
+                    .value_parser(value_parser!(u64).range(1..))
+                    .default_value("30")
+            )
+            // retries of failed requests for all subcommands
+            .arg(
+                Arg::new(ALL_COMMANDS.arg_max_attempts.0)
+                    .help("Maximum number of attempts for requests failing with network errors, timeouts or 502/503/504. Purchases, sales and other POST requests are only resent if they could not be sent at all.")
+                    .id(ALL_COMMANDS.arg_max_attempts.1)
+                    .short(ALL_COMMANDS.arg_max_attempts.2)
+                    .long(ALL_COMMANDS.arg_max_attempts.0)
+                    .value_name("ATTEMPTS")
+                    .value_parser(value_parser!(u32).range(1..=10))
+                    .default_value("3")
+            )
             // subcommand for local status
             .subcommand(
                 Command::new(ALL_COMMANDS.sc_status)
@@ -65,6 +224,15 @@ pub fn cli() -> Command {
                             .exclusive(true)
                             .action(ArgAction::SetTrue)
                     )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_server.0)
+                            .help("To get the status of the SpaceTraders server: version, resets and global stats.")
+                            .id(ALL_COMMANDS.arg_server.1)
+                            .short(ALL_COMMANDS.arg_server.2)
+                            .long(ALL_COMMANDS.arg_server.0)
+                            .exclusive(true)
+                            .action(ArgAction::SetTrue)
+                    )
             )
             // subcommand for new game
             .subcommand(
@@ -78,9 +246,24 @@ pub fn cli() -> Command {
                             .long(ALL_COMMANDS.arg_callsign.0)
                             .required(true)
                     )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_faction.0)
+                            .help("The faction to start in, e.g., COSMIC. See the faction command for all factions.")
+                            .id(ALL_COMMANDS.arg_faction.1)
+                            .short(ALL_COMMANDS.arg_faction.2)
+                            .long(ALL_COMMANDS.arg_faction.0)
+                            .action(ArgAction::Set)
+                            .default_value("COSMIC")
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_email.0)
+                            .help("The email address to reserve the callsign with between resets.")
+                            .id(ALL_COMMANDS.arg_email.1)
+                            .short(ALL_COMMANDS.arg_email.2)
+                            .long(ALL_COMMANDS.arg_email.0)
+                            .action(ArgAction::Set)
+                    )
                     .arg_required_else_help(true),
-                    // TODO: add flag for faction and email:
-                    // https://spacetraders.stoplight.io/docs/spacetraders/86ed6bbe4f5d7-register-new-agent
             )
             // manually set local game status
             .subcommand(
@@ -102,12 +285,12 @@ pub fn cli() -> Command {
                     .about("View locations data. Defaults to view agent headquarter.")
                     .arg(
                         Arg::new(ALL_COMMANDS.arg_waypoint.0)
-                            .help("The waypoint to get data for, e.g., X1-DF55-20250Z.")
+                            .help("The waypoint to get data for, e.g., X1-DF55-20250Z. Shows construction progress if the waypoint is under construction.")
                             .id(ALL_COMMANDS.arg_waypoint.1)
                             .short(ALL_COMMANDS.arg_waypoint.2)
                             .long(ALL_COMMANDS.arg_waypoint.0)
                             .action(ArgAction::Set)
-                            .exclusive(true)
+                            .conflicts_with(ALL_COMMANDS.arg_system.1)
                     )
                     .arg(
                         Arg::new(ALL_COMMANDS.arg_system.0)
@@ -116,8 +299,57 @@ pub fn cli() -> Command {
                             .short(ALL_COMMANDS.arg_system.2)
                             .long(ALL_COMMANDS.arg_system.0)
                             .action(ArgAction::Set)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_type.0)
+                            .help("Only view waypoints of a system with this type, e.g., PLANET, ASTEROID or JUMP_GATE.")
+                            .id(ALL_COMMANDS.arg_type.1)
+                            .short(ALL_COMMANDS.arg_type.2)
+                            .long(ALL_COMMANDS.arg_type.0)
+                            .action(ArgAction::Set)
+                            .requires(ALL_COMMANDS.arg_system.1)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_trait.0)
+                            .help("Only view waypoints of a system with this trait, e.g., MARKETPLACE or SHIPYARD. Can be passed several times to combine traits.")
+                            .id(ALL_COMMANDS.arg_trait.1)
+                            .short(ALL_COMMANDS.arg_trait.2)
+                            .long(ALL_COMMANDS.arg_trait.0)
+                            .action(ArgAction::Append)
+                            .requires(ALL_COMMANDS.arg_system.1)
+                    )
+                    .args(list_args().map(|arg| arg.requires(ALL_COMMANDS.arg_system.1)))
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_jump_gate.0)
+                            .help("The jump gate waypoint to view connected systems for, e.g., X1-DF55-69207D.")
+                            .id(ALL_COMMANDS.arg_jump_gate.1)
+                            .short(ALL_COMMANDS.arg_jump_gate.2)
+                            .long(ALL_COMMANDS.arg_jump_gate.0)
+                            .action(ArgAction::Set)
                             .exclusive(true)
                     )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_known.0)
+                            .help("To view all waypoints stored in the local galaxy database from scans and charts.")
+                            .id(ALL_COMMANDS.arg_known.1)
+                            .short(ALL_COMMANDS.arg_known.2)
+                            .long(ALL_COMMANDS.arg_known.0)
+                            .action(ArgAction::SetTrue)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_offline.0)
+                            .help("To get waypoint or system data from the local galaxy database instead of the API. See the galaxy command.")
+                            .id(ALL_COMMANDS.arg_offline.1)
+                            .short(ALL_COMMANDS.arg_offline.2)
+                            .long(ALL_COMMANDS.arg_offline.0)
+                            .action(ArgAction::SetTrue)
+                            .requires("offline_target")
+                    )
+                    .group(
+                        ArgGroup::new("offline_target")
+                            .args([ALL_COMMANDS.arg_waypoint.1, ALL_COMMANDS.arg_system.1])
+                    )
             )
             // check contracts
             .subcommand(
@@ -150,5 +382,456 @@ pub fn cli() -> Command {
                             .action(ArgAction::Set)
                             .exclusive(true)
                     )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_deliver.0)
+                            .help("Deliver goods for a contract with a docked ship, e.g., clhzd3zrx1sufs60dc58k5vyj MYCALLSIGN-1 IRON_ORE 10")
+                            .id(ALL_COMMANDS.arg_deliver.1)
+                            .short(ALL_COMMANDS.arg_deliver.2)
+                            .long(ALL_COMMANDS.arg_deliver.0)
+                            .value_names(["CONTRACT_ID", "SHIP", "SYMBOL", "UNITS"])
+                            .num_args(4)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_negotiate.0)
+                            .help("The symbol of a ship docked at a faction waypoint to negotiate a new contract with, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_negotiate.1)
+                            .short(ALL_COMMANDS.arg_negotiate.2)
+                            .long(ALL_COMMANDS.arg_negotiate.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .args(list_args())
+            )
+            // check ships
+            .subcommand(
+                Command::new(ALL_COMMANDS.sc_ship)
+                    .about("View and control ships of the agent. Defaults to view all ships of the agent's fleet.")
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_symbol.0)
+                            .help("The symbol of a ship to view data for, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_symbol.1)
+                            .short(ALL_COMMANDS.arg_symbol.2)
+                            .long(ALL_COMMANDS.arg_symbol.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .args(list_args().map(|arg| {
+                        arg.conflicts_with_all([
+                            ALL_COMMANDS.arg_navigate.1,
+                            ALL_COMMANDS.arg_refuel.1,
+                            ALL_COMMANDS.arg_extract.1,
+                            ALL_COMMANDS.arg_wait_for.1,
+                        ])
+                    }))
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_orbit.0)
+                            .help("The symbol of a docked ship to move into orbit, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_orbit.1)
+                            .short(ALL_COMMANDS.arg_orbit.2)
+                            .long(ALL_COMMANDS.arg_orbit.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_dock.0)
+                            .help("The symbol of an orbiting ship to dock at its waypoint, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_dock.1)
+                            .short(ALL_COMMANDS.arg_dock.2)
+                            .long(ALL_COMMANDS.arg_dock.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_navigate.0)
+                            .help("The symbol of a ship and the waypoint to navigate it to, e.g., MYCALLSIGN-1 X1-DF55-20250Z")
+                            .id(ALL_COMMANDS.arg_navigate.1)
+                            .short(ALL_COMMANDS.arg_navigate.2)
+                            .long(ALL_COMMANDS.arg_navigate.0)
+                            .value_names(["SHIP", "WAYPOINT"])
+                            .num_args(2)
+                            .action(ArgAction::Set)
+                            .conflicts_with_all([ALL_COMMANDS.arg_refuel.1, ALL_COMMANDS.arg_extract.1])
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_flight_mode.0)
+                            .help("The symbol of a ship and its new flight mode (CRUISE, BURN, DRIFT or STEALTH), e.g., MYCALLSIGN-1 BURN")
+                            .id(ALL_COMMANDS.arg_flight_mode.1)
+                            .short(ALL_COMMANDS.arg_flight_mode.2)
+                            .long(ALL_COMMANDS.arg_flight_mode.0)
+                            .value_names(["SHIP", "MODE"])
+                            .num_args(2)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_refuel.0)
+                            .help("The symbol of a docked ship to refuel, e.g., MYCALLSIGN-1. Refuels to full capacity by default.")
+                            .id(ALL_COMMANDS.arg_refuel.1)
+                            .short(ALL_COMMANDS.arg_refuel.2)
+                            .long(ALL_COMMANDS.arg_refuel.0)
+                            .action(ArgAction::Set)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_units.0)
+                            .help("The number of fuel units to refuel, e.g., 100.")
+                            .id(ALL_COMMANDS.arg_units.1)
+                            .short(ALL_COMMANDS.arg_units.2)
+                            .long(ALL_COMMANDS.arg_units.0)
+                            .value_parser(value_parser!(u32))
+                            .action(ArgAction::Set)
+                            .requires(ALL_COMMANDS.arg_refuel.1)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_from_cargo.0)
+                            .help("To refuel from fuel held in the ship's cargo instead of buying it.")
+                            .id(ALL_COMMANDS.arg_from_cargo.1)
+                            .short(ALL_COMMANDS.arg_from_cargo.2)
+                            .long(ALL_COMMANDS.arg_from_cargo.0)
+                            .action(ArgAction::SetTrue)
+                            .requires(ALL_COMMANDS.arg_refuel.1)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_extract.0)
+                            .help("The symbol of an orbiting ship to extract resources with, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_extract.1)
+                            .short(ALL_COMMANDS.arg_extract.2)
+                            .long(ALL_COMMANDS.arg_extract.0)
+                            .action(ArgAction::Set)
+                            .conflicts_with(ALL_COMMANDS.arg_refuel.1)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_with_survey.0)
+                            .help("The signature of a stored survey to target the extraction with, e.g., X1-DF55-17335A-3F9E1B")
+                            .id(ALL_COMMANDS.arg_with_survey.1)
+                            .short(ALL_COMMANDS.arg_with_survey.2)
+                            .long(ALL_COMMANDS.arg_with_survey.0)
+                            .action(ArgAction::Set)
+                            .requires(ALL_COMMANDS.arg_extract.1)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_wait.0)
+                            .help("To wait until the navigating ship arrives or the extracting ship's cooldown ends.")
+                            .id(ALL_COMMANDS.arg_wait.1)
+                            .short(ALL_COMMANDS.arg_wait.2)
+                            .long(ALL_COMMANDS.arg_wait.0)
+                            .action(ArgAction::SetTrue)
+                            .requires("wait_target")
+                    )
+                    .group(
+                        ArgGroup::new("wait_target")
+                            .args([ALL_COMMANDS.arg_navigate.1, ALL_COMMANDS.arg_extract.1])
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_wait_for.0)
+                            .help("The symbol of a ship to wait for until it arrives and its cooldown ends, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_wait_for.1)
+                            .short(ALL_COMMANDS.arg_wait_for.2)
+                            .long(ALL_COMMANDS.arg_wait_for.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_survey.0)
+                            .help("The symbol of an orbiting ship to survey its waypoint with, e.g., MYCALLSIGN-1. Surveys are stored locally until they expire.")
+                            .id(ALL_COMMANDS.arg_survey.1)
+                            .short(ALL_COMMANDS.arg_survey.2)
+                            .long(ALL_COMMANDS.arg_survey.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_surveys.0)
+                            .help("To view all locally stored surveys that have not expired yet.")
+                            .id(ALL_COMMANDS.arg_surveys.1)
+                            .short(ALL_COMMANDS.arg_surveys.2)
+                            .long(ALL_COMMANDS.arg_surveys.0)
+                            .action(ArgAction::SetTrue)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_siphon.0)
+                            .help("The symbol of an orbiting ship to siphon gas with, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_siphon.1)
+                            .short(ALL_COMMANDS.arg_siphon.2)
+                            .long(ALL_COMMANDS.arg_siphon.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_jump.0)
+                            .help("The symbol of a ship at a jump gate and the jump gate to jump to in another system, e.g., MYCALLSIGN-1 X1-AB12-34567Z")
+                            .id(ALL_COMMANDS.arg_jump.1)
+                            .short(ALL_COMMANDS.arg_jump.2)
+                            .long(ALL_COMMANDS.arg_jump.0)
+                            .value_names(["SHIP", "WAYPOINT"])
+                            .num_args(2)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_warp.0)
+                            .help("The symbol of a ship with a warp drive and the waypoint to warp to in another system, e.g., MYCALLSIGN-1 X1-AB12-34567Z")
+                            .id(ALL_COMMANDS.arg_warp.1)
+                            .short(ALL_COMMANDS.arg_warp.2)
+                            .long(ALL_COMMANDS.arg_warp.0)
+                            .value_names(["SHIP", "WAYPOINT"])
+                            .num_args(2)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_refine.0)
+                            .help("The symbol of a ship with a refinery and the good to produce, e.g., MYCALLSIGN-1 IRON. Lists the goods the ship can produce from its cargo if no good is passed.")
+                            .id(ALL_COMMANDS.arg_refine.1)
+                            .short(ALL_COMMANDS.arg_refine.2)
+                            .long(ALL_COMMANDS.arg_refine.0)
+                            .value_names(["SHIP", "PRODUCE"])
+                            .num_args(1..=2)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+            )
+            // manage cargo
+            .subcommand(
+                Command::new(ALL_COMMANDS.sc_cargo)
+                    .about("Manage the cargo of a ship: sell, purchase, jettison, transfer or supply goods.")
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_sell.0)
+                            .help("Sell goods from a docked ship at its marketplace, e.g., MYCALLSIGN-1 IRON_ORE 10")
+                            .id(ALL_COMMANDS.arg_sell.1)
+                            .short(ALL_COMMANDS.arg_sell.2)
+                            .long(ALL_COMMANDS.arg_sell.0)
+                            .value_names(["SHIP", "SYMBOL", "UNITS"])
+                            .num_args(3)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_purchase.0)
+                            .help("Purchase goods for a docked ship at its marketplace, e.g., MYCALLSIGN-1 FUEL 10")
+                            .id(ALL_COMMANDS.arg_purchase.1)
+                            .short(ALL_COMMANDS.arg_purchase.2)
+                            .long(ALL_COMMANDS.arg_purchase.0)
+                            .value_names(["SHIP", "SYMBOL", "UNITS"])
+                            .num_args(3)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_jettison.0)
+                            .help("Jettison goods from the cargo of a ship, e.g., MYCALLSIGN-1 ICE_WATER 5")
+                            .id(ALL_COMMANDS.arg_jettison.1)
+                            .short(ALL_COMMANDS.arg_jettison.2)
+                            .long(ALL_COMMANDS.arg_jettison.0)
+                            .value_names(["SHIP", "SYMBOL", "UNITS"])
+                            .num_args(3)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_transfer.0)
+                            .help("Transfer goods to another ship at the same waypoint, e.g., MYCALLSIGN-1 MYCALLSIGN-2 IRON_ORE 10")
+                            .id(ALL_COMMANDS.arg_transfer.1)
+                            .short(ALL_COMMANDS.arg_transfer.2)
+                            .long(ALL_COMMANDS.arg_transfer.0)
+                            .value_names(["SHIP", "TARGET_SHIP", "SYMBOL", "UNITS"])
+                            .num_args(4)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_supply.0)
+                            .help("Supply goods from a docked ship to the construction site at its waypoint, e.g., MYCALLSIGN-1 FAB_MATS 20")
+                            .id(ALL_COMMANDS.arg_supply.1)
+                            .short(ALL_COMMANDS.arg_supply.2)
+                            .long(ALL_COMMANDS.arg_supply.0)
+                            .value_names(["SHIP", "SYMBOL", "UNITS"])
+                            .num_args(3)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg_required_else_help(true)
+            )
+            // check markets
+            .subcommand(
+                Command::new(ALL_COMMANDS.sc_market)
+                    .about("View the market of a waypoint with a marketplace. Trade goods are only shown while a ship is present.")
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_waypoint.0)
+                            .help("The waypoint to get market data for, e.g., X1-DF55-20250Z.")
+                            .id(ALL_COMMANDS.arg_waypoint.1)
+                            .short(ALL_COMMANDS.arg_waypoint.2)
+                            .long(ALL_COMMANDS.arg_waypoint.0)
+                            .action(ArgAction::Set)
+                            .required(true)
+                    )
+                    .arg_required_else_help(true)
+            )
+            // check shipyards and purchase ships
+            .subcommand(
+                Command::new(ALL_COMMANDS.sc_shipyard)
+                    .about("View shipyards and purchase ships.")
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_waypoint.0)
+                            .help("The waypoint to list ship types and prices of its shipyard for, e.g., X1-DF55-20250Z.")
+                            .id(ALL_COMMANDS.arg_waypoint.1)
+                            .short(ALL_COMMANDS.arg_waypoint.2)
+                            .long(ALL_COMMANDS.arg_waypoint.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_system.0)
+                            .help("The system to find all shipyards in, e.g., X1-VS75.")
+                            .id(ALL_COMMANDS.arg_system.1)
+                            .short(ALL_COMMANDS.arg_system.2)
+                            .long(ALL_COMMANDS.arg_system.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_purchase.0)
+                            .help("The ship type to purchase and the waypoint of the shipyard, e.g., SHIP_MINING_DRONE X1-DF55-20250Z")
+                            .id(ALL_COMMANDS.arg_purchase.1)
+                            .short(ALL_COMMANDS.arg_purchase.2)
+                            .long(ALL_COMMANDS.arg_purchase.0)
+                            .value_names(["SHIP_TYPE", "WAYPOINT"])
+                            .num_args(2)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg_required_else_help(true)
+            )
+            // check factions
+            .subcommand(
+                Command::new(ALL_COMMANDS.sc_faction)
+                    .about("View factions and their traits. Defaults to view all factions.")
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_symbol.0)
+                            .help("The symbol of a faction to view data for, e.g., COSMIC")
+                            .id(ALL_COMMANDS.arg_symbol.1)
+                            .short(ALL_COMMANDS.arg_symbol.2)
+                            .long(ALL_COMMANDS.arg_symbol.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .args(list_args())
+            )
+            // manage ship modules and mounts
+            .subcommand(
+                Command::new(ALL_COMMANDS.sc_loadout)
+                    .about("View and change the modules and mounts of a ship.")
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_symbol.0)
+                            .help("The symbol of a ship to view modules and mounts for, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_symbol.1)
+                            .short(ALL_COMMANDS.arg_symbol.2)
+                            .long(ALL_COMMANDS.arg_symbol.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_install_module.0)
+                            .help("Install a module from the cargo of a ship docked at a shipyard, e.g., MYCALLSIGN-1 MODULE_CARGO_HOLD_II")
+                            .id(ALL_COMMANDS.arg_install_module.1)
+                            .short(ALL_COMMANDS.arg_install_module.2)
+                            .long(ALL_COMMANDS.arg_install_module.0)
+                            .value_names(["SHIP", "SYMBOL"])
+                            .num_args(2)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_remove_module.0)
+                            .help("Remove a module of a ship docked at a shipyard into its cargo, e.g., MYCALLSIGN-1 MODULE_CARGO_HOLD_II")
+                            .id(ALL_COMMANDS.arg_remove_module.1)
+                            .short(ALL_COMMANDS.arg_remove_module.2)
+                            .long(ALL_COMMANDS.arg_remove_module.0)
+                            .value_names(["SHIP", "SYMBOL"])
+                            .num_args(2)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_install_mount.0)
+                            .help("Install a mount from the cargo of a ship docked at a shipyard, e.g., MYCALLSIGN-1 MOUNT_MINING_LASER_II")
+                            .id(ALL_COMMANDS.arg_install_mount.1)
+                            .short(ALL_COMMANDS.arg_install_mount.2)
+                            .long(ALL_COMMANDS.arg_install_mount.0)
+                            .value_names(["SHIP", "SYMBOL"])
+                            .num_args(2)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_remove_mount.0)
+                            .help("Remove a mount of a ship docked at a shipyard into its cargo, e.g., MYCALLSIGN-1 MOUNT_MINING_LASER_II")
+                            .id(ALL_COMMANDS.arg_remove_mount.1)
+                            .short(ALL_COMMANDS.arg_remove_mount.2)
+                            .long(ALL_COMMANDS.arg_remove_mount.0)
+                            .value_names(["SHIP", "SYMBOL"])
+                            .num_args(2)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg_required_else_help(true)
+            )
+            // scan surroundings and chart waypoints
+            .subcommand(
+                Command::new(ALL_COMMANDS.sc_scan)
+                    .about("Scan the surroundings of a ship or chart its current waypoint.")
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_systems.0)
+                            .help("The symbol of a ship to scan for nearby systems with, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_systems.1)
+                            .short(ALL_COMMANDS.arg_systems.2)
+                            .long(ALL_COMMANDS.arg_systems.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_waypoints.0)
+                            .help("The symbol of a ship to scan for nearby waypoints with, e.g., MYCALLSIGN-1. Found waypoints are stored in the local galaxy database.")
+                            .id(ALL_COMMANDS.arg_waypoints.1)
+                            .short(ALL_COMMANDS.arg_waypoints.2)
+                            .long(ALL_COMMANDS.arg_waypoints.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_ships.0)
+                            .help("The symbol of a ship to scan for nearby ships with, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_ships.1)
+                            .short(ALL_COMMANDS.arg_ships.2)
+                            .long(ALL_COMMANDS.arg_ships.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_chart.0)
+                            .help("The symbol of a ship to chart its uncharted waypoint with, e.g., MYCALLSIGN-1")
+                            .id(ALL_COMMANDS.arg_chart.1)
+                            .short(ALL_COMMANDS.arg_chart.2)
+                            .long(ALL_COMMANDS.arg_chart.0)
+                            .action(ArgAction::Set)
+                            .exclusive(true)
+                    )
+                    .arg_required_else_help(true)
+            )
+            // sync and view the local galaxy database
+            .subcommand(
+                Command::new(ALL_COMMANDS.sc_galaxy)
+                    .about("Manage the local galaxy database used for offline lookups. Defaults to view its sync status.")
+                    .arg(
+                        Arg::new(ALL_COMMANDS.arg_sync.0)
+                            .help("To crawl all systems and their waypoints into the local galaxy database. Resumes an interrupted sync.")
+                            .id(ALL_COMMANDS.arg_sync.1)
+                            .short(ALL_COMMANDS.arg_sync.2)
+                            .long(ALL_COMMANDS.arg_sync.0)
+                            .action(ArgAction::SetTrue)
+                            .exclusive(true)
+                    )
             )
 }
@@ -0,0 +1,210 @@
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+
+// SpaceTraders error codes the CLI reacts to
+pub const COOLDOWN_CONFLICT: u32 = 4000;
+pub const SHIP_IN_TRANSIT: u32 = 4214;
+pub const INSUFFICIENT_FUNDS: u32 = 4216;
+pub const MARKET_INSUFFICIENT_CREDITS: u32 = 4600;
+
+#[derive(Debug)]
+pub enum Error {
+    // Request could not be sent or no response was received
+    Transport(reqwest::Error),
+    // Unsuccessful response without a SpaceTraders error body
+    Http {
+        context: String,
+        status: StatusCode,
+        body: String,
+    },
+    // Unsuccessful response with a SpaceTraders error code and its data payload
+    Api {
+        context: String,
+        status: StatusCode,
+        code: u32,
+        message: String,
+        data: Value,
+    },
+    // Response did not match the expected model
+    Deserialize {
+        context: String,
+        message: String,
+    },
+    // Local token, surveys, waypoints or galaxy data are missing or unusable
+    LocalState(String),
+    // Command was used wrongly or cannot be run in the current game state
+    Command(String),
+}
+
+// Error body returned by the API for unsuccessful requests
+#[derive(Deserialize)]
+struct ApiErrorBody {
+    error: ApiError,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    code: u32,
+    message: String,
+    #[serde(default)]
+    data: Value,
+}
+
+impl Error {
+    pub fn from_response(context: &str, status: StatusCode, body: &str) -> Error {
+        // Keep code and data of SpaceTraders errors, fall back to the plain HTTP status
+        match serde_json::from_str::<ApiErrorBody>(body) {
+            Ok(api_error) => Error::Api {
+                context: context.to_string(),
+                status,
+                code: api_error.error.code,
+                message: api_error.error.message,
+                data: api_error.error.data,
+            },
+            Err(_) => Error::Http {
+                context: context.to_string(),
+                status,
+                body: body.to_string(),
+            },
+        }
+    }
+
+    pub fn api_code(&self) -> Option<u32> {
+        match self {
+            Error::Api { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    pub fn hint(&self) -> Option<&'static str> {
+        // Suggest a next step for errors the user can resolve
+        match self.api_code()? {
+            SHIP_IN_TRANSIT | COOLDOWN_CONFLICT => {
+                Some("Use the ship command with --wait-for SHIP to wait until the ship is ready.")
+            }
+            INSUFFICIENT_FUNDS | MARKET_INSUFFICIENT_CREDITS => {
+                Some("Check your credits with the status command.")
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Transport(err) => write!(f, "Request failed - {}", err),
+            Error::Http {
+                context,
+                status,
+                body,
+            } => write!(f, "{} - HTTP {} {}", context, status, body),
+            Error::Api {
+                context,
+                code,
+                message,
+                ..
+            } => write!(f, "{} - {} (code {})", context, message, code),
+            Error::Deserialize { context, message } => {
+                write!(f, "{} - unexpected response: {}", context, message)
+            }
+            Error::LocalState(message) | Error::Command(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Error {
+        Error::Transport(err)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Error {
+        Error::LocalState(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_response_keeps_api_code_and_data() {
+        let body = json!({
+            "error": {
+                "message": "Ship is currently in-transit.",
+                "code": SHIP_IN_TRANSIT,
+                "data": { "arrival": "2023-11-18T12:00:00.000Z" }
+            }
+        })
+        .to_string();
+        let err = Error::from_response("Error navigating ship", StatusCode::BAD_REQUEST, &body);
+
+        match &err {
+            Error::Api {
+                context,
+                status,
+                code,
+                message,
+                data,
+            } => {
+                assert_eq!(context, "Error navigating ship");
+                assert_eq!(*status, StatusCode::BAD_REQUEST);
+                assert_eq!(*code, SHIP_IN_TRANSIT);
+                assert_eq!(message, "Ship is currently in-transit.");
+                assert_eq!(data["arrival"], "2023-11-18T12:00:00.000Z");
+            }
+            other => panic!("expected Error::Api, got {:?}", other),
+        }
+        assert_eq!(err.api_code(), Some(SHIP_IN_TRANSIT));
+        assert!(err.hint().is_some());
+    }
+
+    #[test]
+    fn from_response_defaults_missing_data_to_null() {
+        let body = json!({ "error": { "message": "Not found.", "code": 404 } }).to_string();
+        let err = Error::from_response("Error getting faction data", StatusCode::NOT_FOUND, &body);
+
+        assert!(matches!(err, Error::Api { ref data, .. } if data.is_null()));
+        assert_eq!(err.api_code(), Some(404));
+        assert!(err.hint().is_none());
+    }
+
+    #[test]
+    fn from_response_falls_back_to_http_for_plain_bodies() {
+        let body = "<html>502 Bad Gateway</html>";
+        let err =
+            Error::from_response("Error getting server status", StatusCode::BAD_GATEWAY, body);
+
+        match &err {
+            Error::Http {
+                context,
+                status,
+                body: err_body,
+            } => {
+                assert_eq!(context, "Error getting server status");
+                assert_eq!(*status, StatusCode::BAD_GATEWAY);
+                assert_eq!(err_body, body);
+            }
+            other => panic!("expected Error::Http, got {:?}", other),
+        }
+        assert_eq!(err.api_code(), None);
+        assert_eq!(
+            err.to_string(),
+            "Error getting server status - HTTP 502 Bad Gateway <html>502 Bad Gateway</html>"
+        );
+    }
+}
@@ -1,51 +1,151 @@
 use clap::ArgMatches;
 use colored::*;
+use reqwest::StatusCode;
+use serde_json::json;
 use std::collections::HashMap;
 use std::io;
 
+use crate::api::models::{
+    CargoUpdate, Charting, ExtractionResult, FlightModeUpdate, Modification, NavUpdate, Navigation,
+    Negotiation, Refinement, Refuel, ServerStatus, Ship, ShipScan, SurveyResult, SystemScan,
+    SystemTravel, Trade, WaypointScan,
+};
 use crate::api::requests;
 use crate::cli::ALL_COMMANDS;
+use crate::error::Error;
+use crate::utils::display;
+use crate::utils::galaxy::GalaxyDb;
 use crate::utils::helpers as hlp;
 use crate::utils::status;
 
+// Number of crawled systems after which the galaxy sync reports its progress
+const GALAXY_SYNC_REPORT_EVERY: usize = 10;
+
+const FLIGHT_MODES: [&str; 4] = ["CRUISE", "BURN", "DRIFT", "STEALTH"];
+
+// Refinery outputs with the raw good they are made from and the module needed to produce them
+const REFINERY_OUTPUTS: [(&str, &str, &str); 9] = [
+    ("IRON", "IRON_ORE", "MODULE_ORE_REFINERY_I"),
+    ("COPPER", "COPPER_ORE", "MODULE_ORE_REFINERY_I"),
+    ("SILVER", "SILVER_ORE", "MODULE_ORE_REFINERY_I"),
+    ("GOLD", "GOLD_ORE", "MODULE_ORE_REFINERY_I"),
+    ("ALUMINUM", "ALUMINUM_ORE", "MODULE_ORE_REFINERY_I"),
+    ("PLATINUM", "PLATINUM_ORE", "MODULE_ORE_REFINERY_I"),
+    ("URANITE", "URANITE_ORE", "MODULE_ORE_REFINERY_I"),
+    ("MERITIUM", "MERITIUM_ORE", "MODULE_ORE_REFINERY_I"),
+    ("FUEL", "HYDROCARBON", "MODULE_FUEL_REFINERY_I"),
+];
+
 pub async fn process_command(
     matches: ArgMatches,
     game_status: &mut HashMap<String, String>,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), Error> {
     // initialize TradersApi struct for API calls
-    let api = requests::get_traders_api();
+    let timeout = hlp::get_timeout(&matches);
+    let max_attempts = hlp::get_max_attempts(&matches);
+    let mut api = requests::get_traders_api(game_status, timeout, max_attempts)?;
+
+    // Detect server resets before remote commands, dropping an invalidated token from the client
+    let mut server_status = None;
+    if !hlp::is_local_command(&matches) {
+        let had_token = status::check_local_token(game_status);
+        server_status = check_server_reset(&api, game_status).await;
+        if had_token && !status::check_local_token(game_status) {
+            api = api.with_game_status(game_status)?;
+        }
+    }
 
     // match subcommands and call api functions
     match matches.subcommand() {
-        Some(("status", sub_matches)) => get_status(api, game_status, sub_matches).await,
+        Some(("status", sub_matches)) => {
+            get_status(api, game_status, server_status, sub_matches).await
+        }
         Some(("new", sub_matches)) => register_new_agent(api, game_status, sub_matches).await,
         Some(("login", sub_matches)) => login_agent(api, game_status, sub_matches).await,
         Some(("location", sub_matches)) => view_location(api, game_status, sub_matches).await,
         Some(("contract", sub_matches)) => view_contract(api, game_status, sub_matches).await,
-        _ => Err(Box::new(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "No command found.",
-        ))),
+        Some(("ship", sub_matches)) => view_ship(api, game_status, sub_matches).await,
+        Some(("cargo", sub_matches)) => manage_cargo(api, game_status, sub_matches).await,
+        Some(("market", sub_matches)) => view_market(api, game_status, sub_matches).await,
+        Some(("shipyard", sub_matches)) => view_shipyard(api, game_status, sub_matches).await,
+        Some(("faction", sub_matches)) => view_faction(api, sub_matches).await,
+        Some(("loadout", sub_matches)) => manage_loadout(api, game_status, sub_matches).await,
+        Some(("scan", sub_matches)) => scan_surroundings(api, game_status, sub_matches).await,
+        Some(("galaxy", sub_matches)) => manage_galaxy(api, game_status, sub_matches).await,
+        _ => Err(Error::Command("No command found.".to_string())),
+    }
+}
+
+pub async fn check_server_reset(
+    api: &requests::TradersApi,
+    game_status: &mut HashMap<String, String>,
+) -> Option<ServerStatus> {
+    // Server status is best effort, a failed request leaves the local status untouched
+    let server_status = api.server_status_req().await.ok()?;
+
+    // Keep the saved reset date if the server sends one that cannot be compared
+    let Some(reset_date) = hlp::parse_reset_date(&server_status.reset_date) else {
+        return Some(server_status);
+    };
+
+    // Compare the server reset date with the one stored at the last run
+    let saved_reset_date = game_status
+        .get("reset_date")
+        .and_then(|saved| hlp::parse_reset_date(saved));
+    if saved_reset_date.is_some_and(|saved| saved != reset_date) {
+        println!(
+            "{} The server was reset on {}. Local surveys and galaxy data were removed.",
+            "Warning!".yellow(),
+            reset_date
+        );
+        if status::check_local_token(game_status) {
+            println!(
+                "The saved token of agent '{}' is no longer valid. Register a new agent with the new command.",
+                game_status.get("callsign").map(String::as_str).unwrap_or("")
+            );
+        }
+        status::invalidate_local_state(game_status);
     }
+    game_status.insert("reset_date".to_string(), reset_date.to_string());
+
+    // Invalidated local status is saved even if the command fails
+    let game_file_path = status::set_home_dir_path(status::GAME_FILE_NAME);
+    status::save_game(&game_file_path, game_status);
+    Some(server_status)
 }
 
 pub async fn get_status(
     api: requests::TradersApi,
     game_status: &HashMap<String, String>,
+    server_status: Option<ServerStatus>,
     sub_matches: &ArgMatches,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // Check if local or remote status is requested
-    if sub_matches.get_flag(ALL_COMMANDS.arg_local.1) {
+) -> Result<(), Error> {
+    // Check if local, server or remote status is requested
+    if sub_matches.get_flag(ALL_COMMANDS.arg_server.1) {
+        // Reuse the server status fetched by the reset check, requesting it only if that failed
+        println!("Getting server status...");
+        let req_result = match server_status {
+            Some(server_status) => server_status,
+            None => api.server_status_req().await?,
+        };
+
+        println!("{}", "Retrieval successful!".green());
+        display::print_server_status(&req_result);
+        Ok(())
+    } else if sub_matches.get_flag(ALL_COMMANDS.arg_local.1) {
         println!("Getting local status...");
         println!(
             "{} {}",
             "callsign: ".green(),
-            game_status.get("callsign").unwrap()
+            game_status
+                .get("callsign")
+                .map(String::as_str)
+                .unwrap_or("")
         );
         println!(
             "{} {}",
             "token: ".green(),
-            game_status.get("token").unwrap()
+            game_status.get("token").map(String::as_str).unwrap_or("")
         );
         Ok(())
     } else if sub_matches.get_flag(ALL_COMMANDS.arg_remote.1)
@@ -57,16 +157,15 @@ pub async fn get_status(
         }
 
         println!("Getting remote status...");
-        let req_result = api.remote_status_req(game_status).await;
+        let req_result = api.remote_status_req().await;
 
         // Check if request was successful
         hlp::act_on_req_result(req_result, "Retrieval successful!", true)
     } else {
         // handle unknown error - should never happen ;)
-        return Err(Box::new(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "Error getting status due to unknown reason.",
-        )));
+        Err(Error::Command(
+            "Error getting status due to unknown reason.".to_string(),
+        ))
     }
 }
 
@@ -74,48 +173,65 @@ pub async fn register_new_agent(
     api: requests::TradersApi,
     game_status: &mut HashMap<String, String>,
     sub_matches: &ArgMatches,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), Error> {
     // Check if local status already has a callsign
     status::overwrite_status_consent(game_status);
 
-    // Get callsign from command line argument and register new agent
+    // Get callsign, faction and email from command line argument
     let callsign = sub_matches
         .get_one::<String>(ALL_COMMANDS.arg_callsign.1)
         .unwrap();
-    let req_resp = api.reg_agent_req(callsign).await;
-
-    // Check if registration was successful
-    match req_resp {
-        Ok(resp_value) => {
-            // Update local status
-            game_status.insert("callsign".to_string(), callsign.to_string());
-            game_status.insert(
-                "token".to_string(),
-                resp_value["data"]["token"]
-                    .to_string()
-                    .trim_matches('"')
-                    .to_string(),
-            );
-            println!("{}", "Registration successful!".green());
-            println!("Registered new agent '{}'.", callsign);
-            println!("{:#?}", resp_value);
-            Ok(())
-        }
-        Err(req_result) => {
-            let req_result_err_msg = req_result.to_string();
-            Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                req_result_err_msg,
-            )))
-        }
+    let faction = sub_matches
+        .get_one::<String>(ALL_COMMANDS.arg_faction.1)
+        .unwrap()
+        .to_uppercase();
+    let email = sub_matches.get_one::<String>(ALL_COMMANDS.arg_email.1);
+
+    // Validate faction before registering
+    let faction_resp = api
+        .faction_data_req(&faction)
+        .await
+        .map_err(|err| match err {
+            Error::Api {
+                status: StatusCode::NOT_FOUND,
+                ..
+            } => Error::Command(format!(
+                "Unknown faction '{}'. Use the faction command to list all factions.",
+                faction
+            )),
+            err => err,
+        })?;
+    if !faction_resp.is_recruiting {
+        return Err(Error::Command(format!(
+            "Faction '{}' is currently not recruiting.",
+            faction
+        )));
     }
+
+    // Register new agent
+    let registration = api.reg_agent_req(callsign, &faction, email).await?;
+
+    // Update local status
+    game_status.insert("callsign".to_string(), callsign.to_string());
+    game_status.insert("token".to_string(), registration.token);
+    println!("{}", "Registration successful!".green());
+    println!("Registered new agent '{}'.", callsign);
+    println!(
+        "{} {}, {} {}",
+        "headquarters:".green(),
+        registration.agent.headquarters,
+        "credits:".green(),
+        registration.agent.credits
+    );
+    display::print_faction(&registration.faction);
+    Ok(())
 }
 
 pub async fn login_agent(
     api: requests::TradersApi,
     game_status: &mut HashMap<String, String>,
     sub_matches: &ArgMatches,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), Error> {
     // Ask for user for token
     println!("Enter token: ");
     let mut token = String::new();
@@ -124,12 +240,7 @@ pub async fn login_agent(
         .expect("Failed to read line");
     let token = match token.trim().parse() {
         Ok(tkn) => tkn,
-        Err(msg) => {
-            return Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                msg,
-            )))
-        }
+        Err(msg) => return Err(Error::Command(format!("Invalid token: {}", msg))),
     };
 
     // Check if local status already has a callsign
@@ -141,9 +252,10 @@ pub async fn login_agent(
         .get_one::<String>(ALL_COMMANDS.arg_callsign.1)
         .unwrap();
 
-    // Update local status and get remote status
+    // Update local status and get remote status with the new token
     let game_status = status::reset_local_status(game_status, callsign.to_string(), token);
-    let req_result = api.remote_status_req(game_status).await;
+    let api = api.with_game_status(game_status)?;
+    let req_result = api.remote_status_req().await;
 
     // Check if login was successful
     hlp::act_on_req_result(req_result, "Login successful!", true)
@@ -153,7 +265,23 @@ pub async fn view_location(
     api: requests::TradersApi,
     game_status: &HashMap<String, String>,
     sub_matches: &ArgMatches,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), Error> {
+    // Offline lookups and known waypoints only need the local galaxy database
+    if sub_matches.get_flag(ALL_COMMANDS.arg_offline.1) {
+        return view_location_offline(sub_matches);
+    }
+    if sub_matches.get_flag(ALL_COMMANDS.arg_known.1) {
+        // Get waypoints stored in the local galaxy database from scans and charts
+        let galaxy_file_path = status::set_home_dir_path(status::GALAXY_FILE_NAME);
+        let galaxy = GalaxyDb::read(&galaxy_file_path)?;
+        let waypoints = galaxy.scanned_waypoint_list();
+        if waypoints.is_empty() {
+            println!("No waypoints stored locally. Scan or chart waypoints first.");
+        }
+        waypoints.into_iter().for_each(display::print_waypoint);
+        return Ok(());
+    }
+
     // Check if token is present
     if !status::check_local_token(game_status) {
         return hlp::no_token_error();
@@ -170,10 +298,17 @@ pub async fn view_location(
         let sys_waypoint_tup = hlp::location_split(waypoint_passed);
 
         // Get waypoint data
-        let req_result = api.loc_waypoint_req(game_status, sys_waypoint_tup).await;
+        let waypoint = api.loc_waypoint_req(sys_waypoint_tup.clone()).await?;
 
-        // Check if location view request was successful
-        hlp::act_on_req_result(req_result, "Retrieval successful!", true)
+        println!("{}", "Retrieval successful!".green());
+        println!("{:#?}", waypoint);
+
+        // Show construction progress for waypoints under construction
+        if waypoint.is_under_construction {
+            let construction = api.loc_construction_req(sys_waypoint_tup).await?;
+            display::print_construction(&construction);
+        }
+        Ok(())
     } else if sub_matches.contains_id(ALL_COMMANDS.arg_system.1) {
         // Get system location from command line argument
         let system_passed = sub_matches
@@ -181,42 +316,98 @@ pub async fn view_location(
             .unwrap();
         println!("Getting data for system {}...", system_passed);
 
+        // Get waypoint type and trait filters from command line argument
+        let filters = hlp::get_waypoint_filters(sub_matches);
+
         // Get system data
-        let req_result = api.loc_system_req(game_status, system_passed).await;
+        let paging = hlp::get_paging(sub_matches);
+        let req_result = api.loc_system_req(system_passed, &filters, &paging).await?;
 
-        // Check if location view request was successful
-        hlp::act_on_req_result(req_result, "Retrieval successful!", true)
+        println!("{}", "Retrieval successful!".green());
+        println!("{:#?}", req_result.data);
+        display::print_list_meta(&req_result.meta);
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_jump_gate.1) {
+        // Get jump gate location from command line argument
+        let gate_passed = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_jump_gate.1)
+            .unwrap();
+        println!("Getting data for jump gate {}...", gate_passed);
+
+        // Divide provided location into system and waypoint coords
+        let sys_waypoint_tup = hlp::location_split(gate_passed);
+
+        // Get jump gate data
+        let req_result = api.loc_jump_gate_req(sys_waypoint_tup).await?;
+
+        println!("{}", "Retrieval successful!".green());
+        display::print_jump_gate(&req_result);
+        Ok(())
     } else {
         println!("Getting data for headquarter waypoint...");
         // Get remote status
-        let status_req_result = api.remote_status_req(game_status).await;
+        let agent = api.remote_status_req().await?;
+        println!("Headquarter detected at {}...", agent.headquarters);
+
+        // Divide provided location into system and waypoint coords
+        let sys_waypoint_tup = hlp::location_split(&agent.headquarters);
+
+        // Get waypoint data
+        let loc_req_result = api.loc_waypoint_req(sys_waypoint_tup).await;
 
         // Check if location view request was successful
-        match status_req_result {
-            Ok(status_req_result) => {
-                let hq_location = status_req_result["data"]
-                    .get("headquarters")
-                    .unwrap()
-                    .to_string();
-                println!("Headquarter detected at {}...", hq_location);
-
-                // Divide provided location into system and waypoint coords
-                let sys_waypoint_tup = hlp::location_split(&hq_location);
-
-                // Get waypoint data
-                let loc_req_result = api.loc_waypoint_req(game_status, sys_waypoint_tup).await;
-
-                // Check if location view request was successful
-                hlp::act_on_req_result(loc_req_result, "Retrieval successful!", true)
-            }
-            Err(status_req_result) => {
-                let status_req_result_err_msg = status_req_result.to_string();
-                return Err(Box::new(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    status_req_result_err_msg,
-                )));
+        hlp::act_on_req_result(loc_req_result, "Retrieval successful!", true)
+    }
+}
+
+fn view_location_offline(sub_matches: &ArgMatches) -> Result<(), Error> {
+    // Read local galaxy database
+    let galaxy_file_path = status::set_home_dir_path(status::GALAXY_FILE_NAME);
+    let galaxy = GalaxyDb::read(&galaxy_file_path)?;
+
+    if let Some(waypoint_passed) = sub_matches.get_one::<String>(ALL_COMMANDS.arg_waypoint.1) {
+        println!("Looking up waypoint {} offline...", waypoint_passed);
+        match galaxy.waypoint(waypoint_passed) {
+            Some(waypoint) => {
+                println!("{}", "Retrieval successful!".green());
+                println!("{:#?}", waypoint);
+                Ok(())
             }
+            None => Err(Error::LocalState(format!(
+                "Waypoint {} not found in the local galaxy database. Run the galaxy command with --sync first.",
+                waypoint_passed
+            ))),
+        }
+    } else {
+        let system_passed = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_system.1)
+            .unwrap();
+        println!("Looking up system {} offline...", system_passed);
+
+        // Query waypoints of the system with the same filters and paging as the API
+        let filters = hlp::get_waypoint_filters(sub_matches);
+        let paging = hlp::get_paging(sub_matches);
+        let query_result = galaxy.system_waypoints(system_passed, &filters, &paging);
+
+        // Systems not synced yet may still hold waypoints found by scans and charts
+        let synced = galaxy.synced_systems.contains(system_passed);
+        if !synced && query_result.meta.total == 0 {
+            return Err(Error::LocalState(format!(
+                "System {} not found in the local galaxy database. Run the galaxy command with --sync first.",
+                system_passed
+            )));
         }
+
+        println!("{}", "Retrieval successful!".green());
+        if !synced {
+            println!(
+                "System {} is not synced, only waypoints found by scans and charts are shown.",
+                system_passed
+            );
+        }
+        println!("{:#?}", query_result.data);
+        display::print_list_meta(&query_result.meta);
+        Ok(())
     }
 }
 
@@ -224,7 +415,7 @@ pub async fn view_contract(
     api: requests::TradersApi,
     game_status: &HashMap<String, String>,
     sub_matches: &ArgMatches,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<(), Error> {
     // Check if token is present
     if !status::check_local_token(game_status) {
         return hlp::no_token_error();
@@ -239,7 +430,7 @@ pub async fn view_contract(
         println!("Getting data for contract {}...", contract_id);
 
         // Get contract data
-        let req_result = api.contract_data_req(game_status, Some(contract_id)).await;
+        let req_result = api.contract_data_req(contract_id).await;
 
         // Check if contract data request was successful
         hlp::act_on_req_result(req_result, "Retrieval successful!", true)
@@ -251,9 +442,8 @@ pub async fn view_contract(
         println!("Accepting contract {}...", contract_id);
 
         // Accept contract
-        let map: HashMap<&str, &str> = HashMap::new();
         let req_result = api
-            .contract_interact_req(game_status, contract_id, "accept", map)
+            .contract_interact_req(contract_id, "accept", json!({}))
             .await;
 
         // Check if contract was accepted
@@ -266,19 +456,944 @@ pub async fn view_contract(
         println!("Fulfilling contract {}...", contract_id);
 
         // Fulfill contract
-        let map: HashMap<&str, &str> = HashMap::new();
         let req_result = api
-            .contract_interact_req(game_status, contract_id, "fulfill", map)
+            .contract_interact_req(contract_id, "fulfill", json!({}))
             .await;
 
         // Check if contract was fulfilled
         hlp::act_on_req_result(req_result, "Contract fulfilled!", true)
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_deliver.1) {
+        // Get contract id, ship, trade symbol and units from command line argument
+        let deliver_args: Vec<&String> = sub_matches
+            .get_many::<String>(ALL_COMMANDS.arg_deliver.1)
+            .unwrap()
+            .collect();
+        let (contract_id, ship_symbol, trade_symbol) =
+            (deliver_args[0], deliver_args[1], deliver_args[2]);
+        let units = hlp::parse_units(deliver_args[3])?;
+        println!(
+            "Delivering {} {} with ship {} for contract {}...",
+            units, trade_symbol, ship_symbol, contract_id
+        );
+
+        // Deliver goods
+        let req_body = json!({
+            "shipSymbol": ship_symbol,
+            "tradeSymbol": trade_symbol,
+            "units": units,
+        });
+        let req_result = api
+            .contract_interact_req(contract_id, "deliver", req_body)
+            .await?;
+
+        println!("{}", "Delivery successful!".green());
+        display::print_delivery_progress(&req_result.contract);
+        if let Some(cargo) = &req_result.cargo {
+            display::print_cargo(cargo);
+        }
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_negotiate.1) {
+        // Get ship symbol from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_negotiate.1)
+            .unwrap();
+        println!("Negotiating new contract with ship {}...", ship_symbol);
+
+        // Negotiate contract
+        let req_result: Negotiation = api
+            .ship_interact_req(ship_symbol, "negotiate", json!({}))
+            .await?;
+        let contract = &req_result.contract;
+
+        println!("{}", "Negotiation successful!".green());
+        display::print_contract(contract);
+
+        // Accept the offered contract right away if the user agrees
+        if !hlp::user_confirms("Accept this contract now?") {
+            return Ok(());
+        }
+        println!("Accepting contract {}...", contract.id);
+        let req_result = api
+            .contract_interact_req(&contract.id, "accept", json!({}))
+            .await;
+
+        // Check if contract was accepted
+        hlp::act_on_req_result(req_result, "Contract accepted!", false)
     } else {
         // Get all contracts data
         println!("Getting data for all your contracts...");
-        let req_result = api.contract_data_req(game_status, None).await;
+        let paging = hlp::get_paging(sub_matches);
+        let req_result = api.contract_list_req(&paging).await?;
 
-        // Check if contract data was retrieved
-        hlp::act_on_req_result(req_result, "Retrieval successful!", true)
+        println!("{}", "Retrieval successful!".green());
+        println!("{:#?}", req_result.data);
+        display::print_list_meta(&req_result.meta);
+        Ok(())
     }
 }
+
+pub async fn view_ship(
+    api: requests::TradersApi,
+    game_status: &HashMap<String, String>,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    // Check if token is present
+    if !status::check_local_token(game_status) {
+        return hlp::no_token_error();
+    }
+
+    if sub_matches.contains_id(ALL_COMMANDS.arg_symbol.1) {
+        // Get ship symbol from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_symbol.1)
+            .unwrap();
+        println!("Getting data for ship {}...", ship_symbol);
+
+        // Get ship data
+        let ship = api.ship_data_req(ship_symbol).await?;
+
+        println!("{}", "Retrieval successful!".green());
+        display::print_ship(&ship);
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_orbit.1) {
+        // Get ship symbol from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_orbit.1)
+            .unwrap();
+        println!("Moving ship {} into orbit...", ship_symbol);
+
+        // Orbit ship
+        let req_result: NavUpdate = api
+            .ship_interact_req(ship_symbol, "orbit", json!({}))
+            .await?;
+
+        println!("{}", "Ship in orbit!".green());
+        display::print_nav(&req_result.nav);
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_dock.1) {
+        // Get ship symbol from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_dock.1)
+            .unwrap();
+        println!("Docking ship {}...", ship_symbol);
+
+        // Dock ship
+        let req_result: NavUpdate = api
+            .ship_interact_req(ship_symbol, "dock", json!({}))
+            .await?;
+
+        println!("{}", "Ship docked!".green());
+        display::print_nav(&req_result.nav);
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_navigate.1) {
+        // Get ship symbol and destination waypoint from command line argument
+        let nav_args: Vec<&String> = sub_matches
+            .get_many::<String>(ALL_COMMANDS.arg_navigate.1)
+            .unwrap()
+            .collect();
+        let (ship_symbol, waypoint) = (nav_args[0], nav_args[1]);
+        println!("Navigating ship {} to {}...", ship_symbol, waypoint);
+
+        // Navigate ship
+        let req_body = json!({ "waypointSymbol": waypoint });
+        let req_result: Navigation = api
+            .ship_interact_req(ship_symbol, "navigate", req_body)
+            .await?;
+
+        println!("{}", "Navigation started!".green());
+        display::print_navigation(&req_result.nav, &req_result.fuel);
+
+        // Block until the ship arrived if requested
+        if sub_matches.get_flag(ALL_COMMANDS.arg_wait.1) {
+            hlp::wait_until(&req_result.nav.route.arrival, "arrival").await?;
+        }
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_flight_mode.1) {
+        // Get ship symbol and flight mode from command line argument
+        let mode_args: Vec<&String> = sub_matches
+            .get_many::<String>(ALL_COMMANDS.arg_flight_mode.1)
+            .unwrap()
+            .collect();
+        let ship_symbol = mode_args[0];
+        let flight_mode = mode_args[1].to_uppercase();
+        if !FLIGHT_MODES.contains(&flight_mode.as_str()) {
+            return Err(Error::Command(format!(
+                "Invalid flight mode '{}'. Choose one of: {}",
+                flight_mode,
+                FLIGHT_MODES.join(", ")
+            )));
+        }
+        println!(
+            "Setting flight mode of ship {} to {}...",
+            ship_symbol, flight_mode
+        );
+
+        // Set flight mode
+        let req_body = json!({ "flightMode": flight_mode });
+        let req_result: FlightModeUpdate = api
+            .ship_interact_req(ship_symbol, "flight_mode", req_body)
+            .await?;
+
+        // Response holds the nav data either directly or nested under "nav"
+        println!("{}", "Flight mode set!".green());
+        match req_result {
+            FlightModeUpdate::Nested(update) => display::print_nav(&update.nav),
+            FlightModeUpdate::Nav(nav) => display::print_nav(&nav),
+        }
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_refuel.1) {
+        // Get ship symbol and refuel options from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_refuel.1)
+            .unwrap();
+        let units = sub_matches.get_one::<u32>(ALL_COMMANDS.arg_units.1);
+        let from_cargo = sub_matches.get_flag(ALL_COMMANDS.arg_from_cargo.1);
+
+        // Ship has to be docked at a marketplace selling fuel, unless fuel is taken from cargo
+        check_refuel_possible(&api, ship_symbol, from_cargo).await?;
+        println!("Refueling ship {}...", ship_symbol);
+
+        // Refuel ship
+        let mut req_body = json!({ "fromCargo": from_cargo });
+        if let Some(units) = units {
+            req_body["units"] = json!(units);
+        }
+        let req_result: Refuel = api
+            .ship_interact_req(ship_symbol, "refuel", req_body)
+            .await?;
+
+        println!("{}", "Ship refueled!".green());
+        display::print_refuel(&req_result.fuel, &req_result.transaction, &req_result.agent);
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_extract.1) {
+        // Get ship symbol from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_extract.1)
+            .unwrap();
+
+        // Extract with a stored survey if a signature is passed
+        let req_result: ExtractionResult =
+            match sub_matches.get_one::<String>(ALL_COMMANDS.arg_with_survey.1) {
+                Some(signature) => {
+                    let survey_file_path = status::set_home_dir_path(status::SURVEY_FILE_NAME);
+                    let survey = status::read_surveys(&survey_file_path)?
+                        .into_iter()
+                        .find(|survey| survey.signature == *signature)
+                        .ok_or_else(|| {
+                            Error::LocalState(format!(
+                                "No valid stored survey with signature {} found.",
+                                signature
+                            ))
+                        })?;
+                    println!(
+                        "Extracting resources with ship {} using survey {}...",
+                        ship_symbol, signature
+                    );
+                    api.ship_interact_req(ship_symbol, "extract_survey", json!(survey))
+                        .await?
+                }
+                None => {
+                    println!("Extracting resources with ship {}...", ship_symbol);
+                    api.ship_interact_req(ship_symbol, "extract", json!({}))
+                        .await?
+                }
+            };
+
+        println!("{}", "Extraction successful!".green());
+        display::print_extraction(&req_result);
+
+        // Block until the cooldown ended if requested
+        if sub_matches.get_flag(ALL_COMMANDS.arg_wait.1) {
+            if let Some(expiration) = &req_result.cooldown.expiration {
+                hlp::wait_until(expiration, "cooldown").await?;
+            }
+        }
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_siphon.1) {
+        // Get ship symbol from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_siphon.1)
+            .unwrap();
+        println!("Siphoning gas with ship {}...", ship_symbol);
+
+        // Siphon resources
+        let req_result: ExtractionResult = api
+            .ship_interact_req(ship_symbol, "siphon", json!({}))
+            .await?;
+
+        println!("{}", "Siphoning successful!".green());
+        display::print_extraction(&req_result);
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_survey.1) {
+        // Get ship symbol from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_survey.1)
+            .unwrap();
+        // Read stored surveys first, so an unreadable file fails before the cooldown is spent
+        let survey_file_path = status::set_home_dir_path(status::SURVEY_FILE_NAME);
+        let mut surveys = status::read_surveys(&survey_file_path)?;
+        println!("Surveying with ship {}...", ship_symbol);
+
+        // Create surveys
+        let req_result: SurveyResult = api
+            .ship_interact_req(ship_symbol, "survey", json!({}))
+            .await?;
+
+        println!("{}", "Survey successful!".green());
+        req_result.surveys.iter().for_each(display::print_survey);
+        display::print_cooldown(&req_result.cooldown);
+
+        // Store new surveys next to the existing ones for later extractions
+        surveys.extend(req_result.surveys);
+        status::save_surveys(&survey_file_path, &surveys)
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_jump.1)
+        || sub_matches.contains_id(ALL_COMMANDS.arg_warp.1)
+    {
+        // Get ship symbol and destination from command line argument
+        let (interact_type, arg_id) = if sub_matches.contains_id(ALL_COMMANDS.arg_jump.1) {
+            ("jump", ALL_COMMANDS.arg_jump.1)
+        } else {
+            ("warp", ALL_COMMANDS.arg_warp.1)
+        };
+        let travel_args: Vec<&String> = sub_matches.get_many::<String>(arg_id).unwrap().collect();
+        let (ship_symbol, waypoint) = (travel_args[0], travel_args[1]);
+        println!(
+            "Requesting {} of ship {} to {}...",
+            interact_type, ship_symbol, waypoint
+        );
+
+        // Jump or warp ship to another system
+        let req_body = json!({ "waypointSymbol": waypoint });
+        let req_result: SystemTravel = api
+            .ship_interact_req(ship_symbol, interact_type, req_body)
+            .await?;
+
+        println!("{}", "Travel started!".green());
+        display::print_system_travel(&req_result);
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_refine.1) {
+        // Get ship symbol and optional good to produce from command line argument
+        let refine_args: Vec<&String> = sub_matches
+            .get_many::<String>(ALL_COMMANDS.arg_refine.1)
+            .unwrap()
+            .collect();
+        let ship_symbol = refine_args[0];
+
+        // Without a good to produce, list what the ship can refine from its cargo
+        let Some(produce) = refine_args.get(1).map(|p| p.to_uppercase()) else {
+            println!("Getting refinery options of ship {}...", ship_symbol);
+            let ship = api.ship_data_req(ship_symbol).await?;
+            println!("{}", "Retrieval successful!".green());
+            print_refinery_options(&ship);
+            return Ok(());
+        };
+        if !REFINERY_OUTPUTS
+            .iter()
+            .any(|(output, _, _)| *output == produce)
+        {
+            return Err(Error::Command(format!(
+                "Invalid refinery output '{}'. Choose one of: {}",
+                produce,
+                REFINERY_OUTPUTS
+                    .iter()
+                    .map(|(output, _, _)| *output)
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )));
+        }
+        println!("Refining {} with ship {}...", produce, ship_symbol);
+
+        // Refine goods
+        let req_body = json!({ "produce": produce });
+        let req_result: Refinement = api
+            .ship_interact_req(ship_symbol, "refine", req_body)
+            .await?;
+
+        println!("{}", "Refining successful!".green());
+        display::print_refine(&req_result);
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_wait_for.1) {
+        // Get ship symbol from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_wait_for.1)
+            .unwrap();
+        println!("Getting data for ship {}...", ship_symbol);
+        let ship = api.ship_data_req(ship_symbol).await?;
+
+        // Wait for arrival first, then for the cooldown if it lasts longer
+        if ship.nav.status == "IN_TRANSIT" {
+            hlp::wait_until(&ship.nav.route.arrival, "arrival").await?;
+        }
+        if let (true, Some(expiration)) = (
+            ship.cooldown.remaining_seconds > 0,
+            &ship.cooldown.expiration,
+        ) {
+            hlp::wait_until(expiration, "cooldown").await?;
+        }
+        println!("{}", format!("Ship {} is ready!", ship_symbol).green());
+        Ok(())
+    } else if sub_matches.get_flag(ALL_COMMANDS.arg_surveys.1) {
+        // Get all stored surveys which are still valid
+        let survey_file_path = status::set_home_dir_path(status::SURVEY_FILE_NAME);
+        let surveys = status::read_surveys(&survey_file_path)?;
+        if surveys.is_empty() {
+            println!("No valid surveys stored.");
+        }
+        surveys.iter().for_each(display::print_survey);
+        Ok(())
+    } else {
+        // Get all ships data
+        println!("Getting data for all your ships...");
+        let paging = hlp::get_paging(sub_matches);
+        let req_result = api.ship_list_req(&paging).await?;
+
+        println!("{}", "Retrieval successful!".green());
+        if req_result.data.is_empty() {
+            println!("No ships found.");
+        }
+        req_result.data.iter().for_each(display::print_ship);
+        display::print_list_meta(&req_result.meta);
+        Ok(())
+    }
+}
+
+async fn check_refuel_possible(
+    api: &requests::TradersApi,
+    ship_symbol: &str,
+    from_cargo: bool,
+) -> Result<(), Error> {
+    // Check that the ship is docked
+    let ship = api.ship_data_req(ship_symbol).await?;
+    let nav = &ship.nav;
+    if nav.status != "DOCKED" {
+        return Err(Error::Command(format!(
+            "Ship {} is not docked ({}). Dock at a marketplace selling fuel first.",
+            ship_symbol, nav.status
+        )));
+    }
+    if from_cargo {
+        return Ok(());
+    }
+
+    // Check that the marketplace at the ship's waypoint trades fuel
+    let waypoint = &nav.waypoint_symbol;
+    let market = api
+        .loc_market_req(hlp::location_split(waypoint))
+        .await
+        .map_err(|err| match err {
+            Error::Api {
+                status: StatusCode::NOT_FOUND,
+                ..
+            } => Error::Command(format!(
+                "Ship {} is not docked at a marketplace.",
+                ship_symbol
+            )),
+            err => err,
+        })?;
+    if !market.trades("FUEL") {
+        return Err(Error::Command(format!(
+            "The marketplace at {} does not sell fuel.",
+            waypoint
+        )));
+    }
+    Ok(())
+}
+
+pub async fn manage_cargo(
+    api: requests::TradersApi,
+    game_status: &HashMap<String, String>,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    // Check if token is present
+    if !status::check_local_token(game_status) {
+        return hlp::no_token_error();
+    }
+
+    if sub_matches.contains_id(ALL_COMMANDS.arg_transfer.1) {
+        // Get ships, trade symbol and units from command line argument
+        let transfer_args: Vec<&String> = sub_matches
+            .get_many::<String>(ALL_COMMANDS.arg_transfer.1)
+            .unwrap()
+            .collect();
+        let (ship_symbol, target_ship, trade_symbol) =
+            (transfer_args[0], transfer_args[1], transfer_args[2]);
+        let units = hlp::parse_units(transfer_args[3])?;
+        println!(
+            "Transferring {} {} from ship {} to ship {}...",
+            units, trade_symbol, ship_symbol, target_ship
+        );
+
+        // Transfer cargo
+        let req_body = json!({
+            "tradeSymbol": trade_symbol,
+            "units": units,
+            "shipSymbol": target_ship,
+        });
+        let req_result: CargoUpdate = api
+            .ship_interact_req(ship_symbol, "transfer", req_body)
+            .await?;
+
+        println!("{}", "Transfer successful!".green());
+        display::print_cargo(&req_result.cargo);
+        return Ok(());
+    }
+
+    if sub_matches.contains_id(ALL_COMMANDS.arg_supply.1) {
+        // Get ship, trade symbol and units from command line argument
+        let supply_args: Vec<&String> = sub_matches
+            .get_many::<String>(ALL_COMMANDS.arg_supply.1)
+            .unwrap()
+            .collect();
+        let (ship_symbol, trade_symbol) = (supply_args[0], supply_args[1]);
+        let units = hlp::parse_units(supply_args[2])?;
+
+        // Construction site is the waypoint the ship is docked at
+        let ship = api.ship_data_req(ship_symbol).await?;
+        let waypoint = ship.nav.waypoint_symbol;
+        println!(
+            "Supplying {} {} from ship {} to construction site {}...",
+            units, trade_symbol, ship_symbol, waypoint
+        );
+
+        // Supply construction site
+        let req_body = json!({
+            "shipSymbol": ship_symbol,
+            "tradeSymbol": trade_symbol,
+            "units": units,
+        });
+        let req_result = api
+            .construction_supply_req(hlp::location_split(&waypoint), req_body)
+            .await?;
+
+        println!("{}", "Supply successful!".green());
+        display::print_construction(&req_result.construction);
+        display::print_cargo(&req_result.cargo);
+        return Ok(());
+    }
+
+    // Sell, purchase and jettison all take a ship, a trade symbol and units
+    let (interact_type, cargo_args) = if sub_matches.contains_id(ALL_COMMANDS.arg_sell.1) {
+        (
+            "sell",
+            sub_matches.get_many::<String>(ALL_COMMANDS.arg_sell.1),
+        )
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_purchase.1) {
+        (
+            "purchase",
+            sub_matches.get_many::<String>(ALL_COMMANDS.arg_purchase.1),
+        )
+    } else {
+        (
+            "jettison",
+            sub_matches.get_many::<String>(ALL_COMMANDS.arg_jettison.1),
+        )
+    };
+    let cargo_args: Vec<&String> = cargo_args.unwrap().collect();
+    let (ship_symbol, trade_symbol) = (cargo_args[0], cargo_args[1]);
+    let units = hlp::parse_units(cargo_args[2])?;
+    println!(
+        "Requesting {} of {} {} for ship {}...",
+        interact_type, units, trade_symbol, ship_symbol
+    );
+
+    // Sell, purchase or jettison cargo
+    let req_body = json!({ "symbol": trade_symbol, "units": units });
+    if interact_type == "jettison" {
+        let req_result: CargoUpdate = api
+            .ship_interact_req(ship_symbol, interact_type, req_body)
+            .await?;
+
+        println!("{}", "Cargo updated!".green());
+        display::print_cargo(&req_result.cargo);
+    } else {
+        let req_result: Trade = api
+            .ship_interact_req(ship_symbol, interact_type, req_body)
+            .await?;
+
+        println!("{}", "Cargo updated!".green());
+        display::print_transaction(&req_result.transaction, &req_result.agent);
+        display::print_cargo(&req_result.cargo);
+    }
+    Ok(())
+}
+
+pub async fn view_market(
+    api: requests::TradersApi,
+    game_status: &HashMap<String, String>,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    // Check if token is present
+    if !status::check_local_token(game_status) {
+        return hlp::no_token_error();
+    }
+
+    // Get waypoint location from command line argument
+    let waypoint_passed = sub_matches
+        .get_one::<String>(ALL_COMMANDS.arg_waypoint.1)
+        .unwrap();
+    println!("Getting market data for waypoint {}...", waypoint_passed);
+
+    // Divide provided location into system and waypoint coords
+    let sys_waypoint_tup = hlp::location_split(waypoint_passed);
+
+    // Get market data
+    let market = api.loc_market_req(sys_waypoint_tup).await?;
+
+    println!("{}", "Retrieval successful!".green());
+    display::print_market(&market);
+    Ok(())
+}
+
+pub async fn view_shipyard(
+    api: requests::TradersApi,
+    game_status: &HashMap<String, String>,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    // Check if token is present
+    if !status::check_local_token(game_status) {
+        return hlp::no_token_error();
+    }
+
+    if sub_matches.contains_id(ALL_COMMANDS.arg_waypoint.1) {
+        // Get waypoint location from command line argument
+        let waypoint_passed = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_waypoint.1)
+            .unwrap();
+        println!("Getting shipyard data for waypoint {}...", waypoint_passed);
+
+        // Divide provided location into system and waypoint coords
+        let sys_waypoint_tup = hlp::location_split(waypoint_passed);
+
+        // Get shipyard data
+        let shipyard = api.loc_shipyard_req(sys_waypoint_tup).await?;
+
+        println!("{}", "Retrieval successful!".green());
+        display::print_shipyard(&shipyard);
+        Ok(())
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_system.1) {
+        // Get system location from command line argument
+        let system_passed = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_system.1)
+            .unwrap();
+        println!("Finding shipyards in system {}...", system_passed);
+
+        // Get system data and keep waypoints with a shipyard
+        let req_result = api
+            .loc_system_req(system_passed, &[], &requests::Paging::All)
+            .await?;
+        let shipyards: Vec<_> = req_result
+            .data
+            .iter()
+            .filter(|waypoint| waypoint.has_trait("SHIPYARD"))
+            .collect();
+
+        println!("{}", "Retrieval successful!".green());
+        if shipyards.is_empty() {
+            println!("No shipyards found in system {}.", system_passed);
+        }
+        shipyards.into_iter().for_each(display::print_waypoint);
+        Ok(())
+    } else {
+        // Get ship type and shipyard waypoint from command line argument
+        let purchase_args: Vec<&String> = sub_matches
+            .get_many::<String>(ALL_COMMANDS.arg_purchase.1)
+            .unwrap()
+            .collect();
+        let (ship_type, waypoint) = (purchase_args[0], purchase_args[1]);
+        println!("Purchasing {} at {}...", ship_type, waypoint);
+
+        // Purchase ship
+        let req_result = api.ship_purchase_req(ship_type, waypoint).await?;
+
+        println!("{}", "Purchase successful!".green());
+        display::print_ship_purchase(&req_result);
+        Ok(())
+    }
+}
+
+pub async fn view_faction(
+    api: requests::TradersApi,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    if sub_matches.contains_id(ALL_COMMANDS.arg_symbol.1) {
+        // Get faction symbol from command line argument
+        let faction_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_symbol.1)
+            .unwrap()
+            .to_uppercase();
+        println!("Getting data for faction {}...", faction_symbol);
+
+        // Get faction data
+        let faction = api.faction_data_req(&faction_symbol).await?;
+
+        println!("{}", "Retrieval successful!".green());
+        display::print_faction(&faction);
+        Ok(())
+    } else {
+        // Get all factions data
+        println!("Getting data for all factions...");
+        let paging = hlp::get_paging(sub_matches);
+        let req_result = api.faction_list_req(&paging).await?;
+
+        println!("{}", "Retrieval successful!".green());
+        req_result.data.iter().for_each(display::print_faction);
+        display::print_list_meta(&req_result.meta);
+        Ok(())
+    }
+}
+
+pub async fn manage_loadout(
+    api: requests::TradersApi,
+    game_status: &HashMap<String, String>,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    // Check if token is present
+    if !status::check_local_token(game_status) {
+        return hlp::no_token_error();
+    }
+
+    if sub_matches.contains_id(ALL_COMMANDS.arg_symbol.1) {
+        // Get ship symbol from command line argument
+        let ship_symbol = sub_matches
+            .get_one::<String>(ALL_COMMANDS.arg_symbol.1)
+            .unwrap();
+        println!("Getting loadout of ship {}...", ship_symbol);
+
+        // Get ship data
+        let ship = api.ship_data_req(ship_symbol).await?;
+
+        println!("{}", "Retrieval successful!".green());
+        display::print_loadout(&ship);
+        return Ok(());
+    }
+
+    // Installing and removing modules and mounts all take a ship and a symbol
+    let (interact_type, arg_id) = if sub_matches.contains_id(ALL_COMMANDS.arg_install_module.1) {
+        ("install_module", ALL_COMMANDS.arg_install_module.1)
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_remove_module.1) {
+        ("remove_module", ALL_COMMANDS.arg_remove_module.1)
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_install_mount.1) {
+        ("install_mount", ALL_COMMANDS.arg_install_mount.1)
+    } else {
+        ("remove_mount", ALL_COMMANDS.arg_remove_mount.1)
+    };
+    let loadout_args: Vec<&String> = sub_matches.get_many::<String>(arg_id).unwrap().collect();
+    let (ship_symbol, symbol) = (loadout_args[0], loadout_args[1]);
+
+    // Get loadout before the change
+    let ship_before = api.ship_data_req(ship_symbol).await?;
+    println!(
+        "Requesting {} of {} for ship {}...",
+        interact_type.replace('_', " "),
+        symbol,
+        ship_symbol
+    );
+
+    // Install or remove module or mount
+    let req_body = json!({ "symbol": symbol });
+    let req_result: Modification = api
+        .ship_interact_req(ship_symbol, interact_type, req_body)
+        .await?;
+
+    // Loadout after the change, taken from the response
+    let mut ship_after = ship_before.clone();
+    ship_after.cargo = req_result.cargo;
+    if let Some(modules) = req_result.modules {
+        ship_after.modules = modules;
+    }
+    if let Some(mounts) = req_result.mounts {
+        ship_after.mounts = mounts;
+    }
+
+    println!("{}", "Loadout changed!".green());
+    println!("{}", "Before:".bold());
+    display::print_loadout(&ship_before);
+    println!("{}", "After:".bold());
+    display::print_loadout(&ship_after);
+    println!(
+        "  {} {} ({} left)",
+        "credits charged:".green(),
+        req_result.transaction.total_price,
+        req_result.agent.credits
+    );
+    Ok(())
+}
+
+pub async fn scan_surroundings(
+    api: requests::TradersApi,
+    game_status: &HashMap<String, String>,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    // Check if token is present
+    if !status::check_local_token(game_status) {
+        return hlp::no_token_error();
+    }
+
+    // Match passed argument to scan or chart interaction
+    let (interact_type, arg_id) = if sub_matches.contains_id(ALL_COMMANDS.arg_systems.1) {
+        ("scan_systems", ALL_COMMANDS.arg_systems.1)
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_waypoints.1) {
+        ("scan_waypoints", ALL_COMMANDS.arg_waypoints.1)
+    } else if sub_matches.contains_id(ALL_COMMANDS.arg_ships.1) {
+        ("scan_ships", ALL_COMMANDS.arg_ships.1)
+    } else {
+        ("chart", ALL_COMMANDS.arg_chart.1)
+    };
+    let ship_symbol = sub_matches.get_one::<String>(arg_id).unwrap();
+    println!(
+        "Requesting {} with ship {}...",
+        interact_type.replace('_', " "),
+        ship_symbol
+    );
+
+    // Scan or chart, each answered with its own data
+    let galaxy_file_path = status::set_home_dir_path(status::GALAXY_FILE_NAME);
+    match interact_type {
+        "scan_systems" => {
+            let req_result: SystemScan = api
+                .ship_interact_req(ship_symbol, interact_type, json!({}))
+                .await?;
+            println!("{}", "Request successful!".green());
+            req_result
+                .systems
+                .iter()
+                .for_each(display::print_scanned_system);
+            display::print_cooldown(&req_result.cooldown);
+        }
+        "scan_waypoints" => {
+            let req_result: WaypointScan = api
+                .ship_interact_req(ship_symbol, interact_type, json!({}))
+                .await?;
+            println!("{}", "Request successful!".green());
+
+            req_result
+                .waypoints
+                .iter()
+                .for_each(display::print_waypoint);
+            display::print_cooldown(&req_result.cooldown);
+
+            // Keep found waypoints in the local galaxy database for the location command
+            let mut galaxy = GalaxyDb::read(&galaxy_file_path)?;
+            galaxy.add_scanned_waypoints(&galaxy_file_path, req_result.waypoints)?;
+        }
+        "scan_ships" => {
+            let req_result: ShipScan = api
+                .ship_interact_req(ship_symbol, interact_type, json!({}))
+                .await?;
+            println!("{}", "Request successful!".green());
+            req_result
+                .ships
+                .iter()
+                .for_each(display::print_scanned_ship);
+            display::print_cooldown(&req_result.cooldown);
+        }
+        _ => {
+            let req_result: Charting = api
+                .ship_interact_req(ship_symbol, interact_type, json!({}))
+                .await?;
+            println!("{}", "Request successful!".green());
+
+            display::print_waypoint(&req_result.waypoint);
+            println!(
+                "  {} {}",
+                "charted by:".green(),
+                req_result.chart.submitted_by.as_deref().unwrap_or("-")
+            );
+
+            // Keep charted waypoint in the local galaxy database for the location command
+            let mut galaxy = GalaxyDb::read(&galaxy_file_path)?;
+            galaxy.add_scanned_waypoints(&galaxy_file_path, vec![req_result.waypoint])?;
+        }
+    }
+    Ok(())
+}
+
+fn print_refinery_options(ship: &Ship) {
+    // Print refinery outputs the ship can produce with its modules from its current cargo
+    let options: Vec<(&str, &str, u32)> = REFINERY_OUTPUTS
+        .iter()
+        .filter(|(_, _, module)| ship.has_module(module))
+        .map(|(output, input, _)| (*output, *input, ship.cargo_units(input)))
+        .filter(|(_, _, units)| *units > 0)
+        .collect();
+    if options.is_empty() {
+        println!("No refinery outputs possible with the current modules and cargo.");
+    }
+    for (output, input, units) in options {
+        println!(
+            "  {} from {} ({} units in cargo)",
+            output.green(),
+            input,
+            units
+        );
+    }
+}
+
+pub async fn manage_galaxy(
+    api: requests::TradersApi,
+    game_status: &HashMap<String, String>,
+    sub_matches: &ArgMatches,
+) -> Result<(), Error> {
+    // Read local galaxy database, which holds the progress of earlier syncs
+    let galaxy_file_path = status::set_home_dir_path(status::GALAXY_FILE_NAME);
+    let mut galaxy = GalaxyDb::read(&galaxy_file_path)?;
+
+    if !sub_matches.get_flag(ALL_COMMANDS.arg_sync.1) {
+        display::print_galaxy_status(&galaxy);
+        return Ok(());
+    }
+
+    // Check if token is present
+    if !status::check_local_token(game_status) {
+        return hlp::no_token_error();
+    }
+
+    // Repair an interrupted earlier sync before appending new records
+    GalaxyDb::repair(&galaxy_file_path)?;
+
+    // Crawl the system list page by page, saving each page as it arrives
+    println!("Syncing systems...");
+    while !galaxy.systems_complete() {
+        let page = galaxy.systems_pages_done + 1;
+        let paging = requests::Paging::Page {
+            page,
+            limit: requests::MAX_PAGE_LIMIT,
+        };
+        let req_result = api.galaxy_systems_req(&paging).await?;
+        if req_result.data.is_empty() {
+            break;
+        }
+        galaxy.add_systems_page(
+            &galaxy_file_path,
+            page,
+            req_result.meta.total,
+            req_result.data,
+        )?;
+        println!(
+            "  {}/{} systems",
+            galaxy.systems.len(),
+            galaxy.systems_total
+        );
+    }
+
+    // Crawl the waypoints of every system not synced yet
+    let unsynced = galaxy.unsynced_systems();
+    println!("Syncing waypoints of {} systems...", unsynced.len());
+    for (i, system_symbol) in unsynced.iter().enumerate() {
+        let req_result = api
+            .loc_system_req(system_symbol, &[], &requests::Paging::All)
+            .await?;
+        galaxy.add_system_waypoints(&galaxy_file_path, system_symbol, req_result.data)?;
+        if (i + 1) % GALAXY_SYNC_REPORT_EVERY == 0 {
+            println!("  {}/{} systems", i + 1, unsynced.len());
+        }
+    }
+
+    // Merge the records appended during the sync into one record per system
+    GalaxyDb::compact(&galaxy_file_path)?;
+    let galaxy = GalaxyDb::read(&galaxy_file_path)?;
+
+    println!("{}", "Galaxy sync complete!".green());
+    display::print_galaxy_status(&galaxy);
+    Ok(())
+}
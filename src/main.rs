@@ -1,5 +1,6 @@
 pub mod api;
 pub mod cli;
+pub mod error;
 pub mod handler;
 pub mod utils;
 
@@ -15,8 +16,7 @@ async fn main() {
     let matches = cli().get_matches();
 
     // set game status file path
-    const GAME_FILE_NAME: &str = ".spacetraders";
-    let game_file_path = status::set_home_dir_path(GAME_FILE_NAME);
+    let game_file_path = status::set_home_dir_path(status::GAME_FILE_NAME);
 
     // read exising game status if available
     let mut game_status = status::read_game(&game_file_path);
@@ -24,8 +24,11 @@ async fn main() {
     // initialize TradersApi struct for API calls
     let process_result = process_command(matches, &mut game_status).await;
 
-    if process_result.is_err() {
-        println!("{} {}", "Error! ".red(), process_result.unwrap_err());
+    if let Err(err) = process_result {
+        println!("{} {}", "Error! ".red(), err);
+        if let Some(hint) = err.hint() {
+            println!("{}", hint);
+        }
         exit(1);
     }
 
@@ -0,0 +1,516 @@
+use colored::Colorize;
+use std::fmt::Display;
+
+use crate::api::models::{
+    Agent, Construction, Contract, Cooldown, ExtractionResult, Faction, JumpGate, Market,
+    MarketTransaction, Meta, Refinement, ScannedShip, ServerStatus, Ship, ShipCargo, ShipFuel,
+    ShipNav, ShipPurchase, Shipyard, Survey, System, SystemTravel, Waypoint,
+};
+use crate::utils::galaxy::GalaxyDb;
+
+fn option_str<T: Display>(value: &Option<T>) -> String {
+    // Render an optional field, showing a dash if it is missing
+    match value {
+        Some(value) => value.to_string(),
+        None => "-".to_string(),
+    }
+}
+
+pub fn print_list_meta(meta: &Meta) {
+    // Print which part of a paginated list is shown
+    println!(
+        "Page {} of {} ({} items in total).",
+        meta.page,
+        meta.total.div_ceil(meta.limit.max(1)).max(1),
+        meta.total
+    );
+}
+
+pub fn print_ship(ship: &Ship) {
+    // Print a short summary of a ship: nav status, location, fuel, cargo and cooldown
+    println!(
+        "{} {} ({})",
+        "Ship".cyan().bold(),
+        ship.symbol.bold(),
+        ship.registration.role
+    );
+    print_nav(&ship.nav);
+    println!(
+        "  {} {}/{}",
+        "fuel:".green(),
+        ship.fuel.current,
+        ship.fuel.capacity
+    );
+    print_cargo(&ship.cargo);
+    print_cooldown(&ship.cooldown);
+}
+
+pub fn print_nav(nav: &ShipNav) {
+    // Print navigation status, location and flight mode of a ship
+    println!(
+        "  {} {} at {} ({})",
+        "nav:".green(),
+        nav.status,
+        nav.waypoint_symbol,
+        nav.flight_mode
+    );
+    if nav.status == "IN_TRANSIT" {
+        println!(
+            "  {} {} -> {}, arriving {}",
+            "route:".green(),
+            nav.route.origin.symbol,
+            nav.route.destination.symbol,
+            nav.route.arrival
+        );
+    }
+}
+
+pub fn print_cargo(cargo: &ShipCargo) {
+    // Print cargo capacity and the goods held
+    println!("  {} {}/{}", "cargo:".green(), cargo.units, cargo.capacity);
+    for item in &cargo.inventory {
+        println!("    {} x{}", item.symbol, item.units);
+    }
+}
+
+pub fn print_cooldown(cooldown: &Cooldown) {
+    // Print remaining cooldown of a ship, if any
+    if cooldown.remaining_seconds > 0 {
+        println!(
+            "  {} {}s remaining (expires {})",
+            "cooldown:".green(),
+            cooldown.remaining_seconds,
+            option_str(&cooldown.expiration)
+        );
+    } else {
+        println!("  {} none", "cooldown:".green());
+    }
+}
+
+pub fn print_navigation(nav: &ShipNav, fuel: &ShipFuel) {
+    // Print departure, arrival time and fuel consumed of a navigation
+    println!(
+        "  {} {} at {}",
+        "departure:".green(),
+        nav.route.origin.symbol,
+        nav.route.departure_time
+    );
+    println!(
+        "  {} {} at {}",
+        "arrival:".green(),
+        nav.route.destination.symbol,
+        nav.route.arrival
+    );
+    println!(
+        "  {} {} ({}/{} left)",
+        "fuel consumed:".green(),
+        option_str(&fuel.consumed.as_ref().map(|consumed| consumed.amount)),
+        fuel.current,
+        fuel.capacity
+    );
+}
+
+pub fn print_refuel(fuel: &ShipFuel, transaction: &MarketTransaction, agent: &Agent) {
+    // Print fuel level, credits spent and the agent's new balance after refueling
+    println!("  {} {}/{}", "fuel:".green(), fuel.current, fuel.capacity);
+    println!(
+        "  {} {} ({} units)",
+        "credits spent:".green(),
+        transaction.total_price,
+        transaction.units
+    );
+    println!("  {} {}", "new balance:".green(), agent.credits);
+}
+
+pub fn print_extraction(extraction: &ExtractionResult) {
+    // Print the yield of an extraction or siphon, the cargo now held and the cooldown
+    let extract_yield = &extraction.extraction.extracted;
+    println!(
+        "  {} {} x{}",
+        "yield:".green(),
+        extract_yield.symbol,
+        extract_yield.units
+    );
+    print_cargo(&extraction.cargo);
+    print_cooldown(&extraction.cooldown);
+}
+
+pub fn print_survey(survey: &Survey) {
+    // Print signature, size, deposits and expiration of a survey
+    let deposits: Vec<&str> = survey
+        .deposits
+        .iter()
+        .map(|deposit| deposit.symbol.as_str())
+        .collect();
+    println!(
+        "{} {} at {} ({})",
+        "Survey".cyan().bold(),
+        survey.signature.bold(),
+        survey.symbol,
+        survey.size
+    );
+    println!("  {} {}", "deposits:".green(), deposits.join(", "));
+    println!("  {} {}", "expires:".green(), survey.expiration);
+}
+
+pub fn print_transaction(transaction: &MarketTransaction, agent: &Agent) {
+    // Print a market transaction: price per unit, total price and the agent's new credits
+    println!(
+        "  {} {} {} x{} at {}",
+        "transaction:".green(),
+        transaction.transaction_type,
+        transaction.trade_symbol,
+        transaction.units,
+        transaction.waypoint_symbol
+    );
+    println!(
+        "  {} {}",
+        "price per unit:".green(),
+        transaction.price_per_unit
+    );
+    println!("  {} {}", "total:".green(), transaction.total_price);
+    println!("  {} {}", "new credits:".green(), agent.credits);
+}
+
+pub fn print_market(market: &Market) {
+    // Print imports, exports and exchange goods of a market, plus trade goods if visible
+    println!("{} {}", "Market".cyan().bold(), market.symbol.bold());
+    for (label, goods) in [
+        ("imports:", &market.imports),
+        ("exports:", &market.exports),
+        ("exchange:", &market.exchange),
+    ] {
+        let goods: Vec<&str> = goods.iter().map(|g| g.symbol.as_str()).collect();
+        println!("  {} {}", label.green(), goods.join(", "));
+    }
+
+    // Trade goods are only returned while one of the agent's ships is at the waypoint
+    match &market.trade_goods {
+        Some(trade_goods) => {
+            println!(
+                "  {:<28}{:>10}{:>10}{:>10}{:>12}",
+                "trade good".green(),
+                "buy".green(),
+                "sell".green(),
+                "volume".green(),
+                "supply".green()
+            );
+            for good in trade_goods {
+                println!(
+                    "  {:<28}{:>10}{:>10}{:>10}{:>12}",
+                    good.symbol,
+                    good.purchase_price,
+                    good.sell_price,
+                    good.trade_volume,
+                    good.supply
+                );
+            }
+        }
+        None => println!("  No ship present, trade goods and prices are not visible."),
+    }
+}
+
+pub fn print_waypoint(waypoint: &Waypoint) {
+    // Print symbol, type, coordinates and traits of a waypoint
+    let traits: Vec<&str> = waypoint.traits.iter().map(|t| t.symbol.as_str()).collect();
+    println!(
+        "{} {} ({}) at ({}, {})",
+        "Waypoint".cyan().bold(),
+        waypoint.symbol.bold(),
+        waypoint.waypoint_type,
+        waypoint.x,
+        waypoint.y
+    );
+    println!("  {} {}", "traits:".green(), traits.join(", "));
+}
+
+pub fn print_shipyard(shipyard: &Shipyard) {
+    // Print ship types of a shipyard, plus prices if a ship is present
+    println!("{} {}", "Shipyard".cyan().bold(), shipyard.symbol.bold());
+
+    // Ship details and prices are only returned while one of the agent's ships is at the waypoint
+    match &shipyard.ships {
+        Some(ships) => {
+            for ship in ships {
+                println!(
+                    "  {:<28}{:>12}  {}",
+                    ship.ship_type, ship.purchase_price, ship.name
+                );
+            }
+        }
+        None => {
+            for ship_type in &shipyard.ship_types {
+                println!("  {}", ship_type.ship_type);
+            }
+            println!("  No ship present, prices are not visible.");
+        }
+    }
+}
+
+pub fn print_ship_purchase(purchase: &ShipPurchase) {
+    // Print the new ship's symbol and loadout, the price paid and the agent's new credits
+    let ship = &purchase.ship;
+    let modules: Vec<&str> = ship.modules.iter().map(|m| m.symbol.as_str()).collect();
+    let mounts: Vec<&str> = ship.mounts.iter().map(|m| m.symbol.as_str()).collect();
+    println!(
+        "{} {} ({})",
+        "Ship".cyan().bold(),
+        ship.symbol.bold(),
+        ship.registration.role
+    );
+    println!("  {} {}", "frame:".green(), ship.frame.symbol);
+    println!("  {} {}", "reactor:".green(), ship.reactor.symbol);
+    println!("  {} {}", "engine:".green(), ship.engine.symbol);
+    println!("  {} {}", "modules:".green(), modules.join(", "));
+    println!("  {} {}", "mounts:".green(), mounts.join(", "));
+    println!("  {} {}", "price:".green(), purchase.transaction.price);
+    println!("  {} {}", "new credits:".green(), purchase.agent.credits);
+}
+
+pub fn print_delivery_progress(contract: &Contract) {
+    // Print the delivery progress of each term of a contract
+    println!("{} {}", "Contract".cyan().bold(), contract.id.bold());
+    print_deliveries(contract);
+}
+
+pub fn print_contract(contract: &Contract) {
+    // Print the offered terms of a contract: deadlines, payments and goods to deliver
+    println!(
+        "{} {} ({} for {})",
+        "Contract".cyan().bold(),
+        contract.id.bold(),
+        contract.contract_type,
+        contract.faction_symbol
+    );
+    println!(
+        "  {} {}",
+        "accept until:".green(),
+        option_str(&contract.deadline_to_accept)
+    );
+    println!("  {} {}", "deadline:".green(), contract.terms.deadline);
+    println!(
+        "  {} {} on accept, {} on fulfill",
+        "payment:".green(),
+        contract.terms.payment.on_accepted,
+        contract.terms.payment.on_fulfilled
+    );
+    print_deliveries(contract);
+}
+
+fn print_deliveries(contract: &Contract) {
+    // Print goods to deliver for a contract with their fulfilled and required units
+    for delivery in &contract.terms.deliver {
+        println!(
+            "  {} {}/{} to {}",
+            delivery.trade_symbol.green(),
+            delivery.units_fulfilled,
+            delivery.units_required,
+            delivery.destination_symbol
+        );
+    }
+}
+
+pub fn print_faction(faction: &Faction) {
+    // Print symbol, name, headquarters, recruitment and traits of a faction
+    let traits: Vec<&str> = faction.traits.iter().map(|t| t.name.as_str()).collect();
+    println!(
+        "{} {} ({})",
+        "Faction".cyan().bold(),
+        faction.symbol.bold(),
+        faction.name
+    );
+    println!(
+        "  {} {}",
+        "headquarters:".green(),
+        option_str(&faction.headquarters)
+    );
+    println!("  {} {}", "recruiting:".green(), faction.is_recruiting);
+    println!("  {} {}", "traits:".green(), traits.join(", "));
+}
+
+pub fn print_jump_gate(jump_gate: &JumpGate) {
+    // Print the systems a jump gate connects to
+    println!("{} {}", "Jump gate".cyan().bold(), jump_gate.symbol.bold());
+    if jump_gate.connections.is_empty() {
+        println!("  No connections, the jump gate may still be under construction.");
+    }
+    for connection in &jump_gate.connections {
+        println!("  {} {}", "connection:".green(), connection);
+    }
+}
+
+pub fn print_system_travel(travel: &SystemTravel) {
+    // Print destination system, cost and cooldown of a jump or warp
+    let nav = &travel.nav;
+    println!(
+        "  {} {} ({})",
+        "destination:".green(),
+        nav.system_symbol,
+        nav.route.destination.symbol
+    );
+    if let Some(transaction) = &travel.transaction {
+        println!(
+            "  {} {} credits ({} left)",
+            "cost:".green(),
+            transaction.total_price,
+            option_str(&travel.agent.as_ref().map(|agent| agent.credits))
+        );
+    }
+    if let Some(fuel) = &travel.fuel {
+        println!(
+            "  {} {} fuel ({}/{} left)",
+            "cost:".green(),
+            option_str(&fuel.consumed.as_ref().map(|consumed| consumed.amount)),
+            fuel.current,
+            fuel.capacity
+        );
+        println!("  {} {}", "arrival:".green(), nav.route.arrival);
+    }
+    if let Some(cooldown) = &travel.cooldown {
+        print_cooldown(cooldown);
+    }
+}
+
+pub fn print_loadout(ship: &Ship) {
+    // Print the capabilities of a ship: capacities, modules and mounts
+    println!(
+        "{} {} ({})",
+        "Ship".cyan().bold(),
+        ship.symbol.bold(),
+        ship.frame.symbol
+    );
+    println!(
+        "  {} cargo {}, fuel {}, module slots {}, mounting points {}",
+        "capacity:".green(),
+        ship.cargo.capacity,
+        ship.fuel.capacity,
+        ship.frame.module_slots,
+        ship.frame.mounting_points
+    );
+    for module in &ship.modules {
+        println!(
+            "  {} {} (capacity {})",
+            "module:".green(),
+            module.symbol,
+            option_str(&module.capacity)
+        );
+    }
+    for mount in &ship.mounts {
+        println!(
+            "  {} {} (strength {})",
+            "mount:".green(),
+            mount.symbol,
+            option_str(&mount.strength)
+        );
+    }
+}
+
+pub fn print_scanned_system(system: &System) {
+    // Print symbol, type, coordinates and distance of a scanned system
+    println!(
+        "{} {} ({}) at ({}, {}), distance {}",
+        "System".cyan().bold(),
+        system.symbol.bold(),
+        system.system_type,
+        system.x,
+        system.y,
+        option_str(&system.distance)
+    );
+}
+
+pub fn print_scanned_ship(ship: &ScannedShip) {
+    // Print symbol, role, frame and location of a scanned ship
+    println!(
+        "{} {} ({}, {})",
+        "Ship".cyan().bold(),
+        ship.symbol.bold(),
+        ship.registration.role,
+        option_str(&ship.frame.as_ref().map(|frame| &frame.symbol))
+    );
+    print_nav(&ship.nav);
+}
+
+pub fn print_refine(refinement: &Refinement) {
+    // Print goods consumed and produced by refining, the cargo now held and the cooldown
+    for (label, goods) in [
+        ("consumed:", &refinement.consumed),
+        ("produced:", &refinement.produced),
+    ] {
+        for good in goods {
+            println!("  {} {} x{}", label.green(), good.trade_symbol, good.units);
+        }
+    }
+    print_cargo(&refinement.cargo);
+    print_cooldown(&refinement.cooldown);
+}
+
+pub fn print_construction(construction: &Construction) {
+    // Print required and fulfilled materials of a construction site and whether it is complete
+    println!(
+        "{} {} ({})",
+        "Construction".cyan().bold(),
+        construction.symbol.bold(),
+        if construction.is_complete {
+            "complete"
+        } else {
+            "in progress"
+        }
+    );
+    for material in &construction.materials {
+        println!(
+            "  {} {}/{}",
+            material.trade_symbol.green(),
+            material.fulfilled,
+            material.required
+        );
+    }
+}
+
+pub fn print_galaxy_status(galaxy: &GalaxyDb) {
+    // Print how much of the galaxy is stored in the local database
+    println!("{}", "Galaxy database".cyan().bold());
+    println!(
+        "  {} {}/{}",
+        "systems:".green(),
+        galaxy.systems.len(),
+        galaxy.systems_total
+    );
+    println!(
+        "  {} {}/{}",
+        "systems with waypoints:".green(),
+        galaxy.synced_systems.len(),
+        galaxy.systems.len()
+    );
+    println!("  {} {}", "waypoints:".green(), galaxy.waypoints.len());
+    println!(
+        "  {} {}",
+        "waypoints from scans:".green(),
+        galaxy.scanned_waypoints.len()
+    );
+}
+
+pub fn print_server_status(server_status: &ServerStatus) {
+    // Print version, reset dates and global stats of the server
+    println!(
+        "{} {} ({})",
+        "Server".cyan().bold(),
+        server_status.version.bold(),
+        server_status.status
+    );
+    println!("  {} {}", "last reset:".green(), server_status.reset_date);
+    println!(
+        "  {} {} ({})",
+        "next reset:".green(),
+        server_status.server_resets.next,
+        server_status.server_resets.frequency
+    );
+    let stats = &server_status.stats;
+    println!(
+        "  {} {} agents, {} ships, {} systems, {} waypoints",
+        "stats:".green(),
+        stats.agents,
+        stats.ships,
+        stats.systems,
+        stats.waypoints
+    );
+}
@@ -0,0 +1,460 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+
+use crate::api::models::{Meta, Page, System, Waypoint};
+use crate::api::requests::Paging;
+use crate::error::Error;
+
+// Local snapshot of the universe, filled by galaxy sync and queried offline
+#[derive(Default)]
+pub struct GalaxyDb {
+    // Number of system list pages already crawled
+    pub systems_pages_done: u32,
+    // Total number of systems reported by the API
+    pub systems_total: u64,
+    // Systems keyed by system symbol
+    pub systems: BTreeMap<String, System>,
+    // Waypoints keyed by waypoint symbol
+    pub waypoints: BTreeMap<String, Waypoint>,
+    // Systems whose waypoints were crawled completely
+    pub synced_systems: BTreeSet<String>,
+    // Waypoints found by scans and charts
+    pub scanned_waypoints: BTreeSet<String>,
+}
+
+// The database file is a journal with one record per line, so progress is saved by appending
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum GalaxyRecord {
+    // Page of the system list with the total number of systems at the time
+    Systems {
+        page: u32,
+        total: u64,
+        systems: Vec<System>,
+    },
+    // All waypoints of a system, which marks the system as synced
+    SystemWaypoints {
+        system_symbol: String,
+        waypoints: Vec<Waypoint>,
+    },
+    // Waypoints found by scans and charts, replacing older data of the same waypoints
+    ScannedWaypoints {
+        waypoints: Vec<Waypoint>,
+    },
+}
+
+impl GalaxyDb {
+    pub fn read(galaxy_file_path: &PathBuf) -> Result<GalaxyDb, Error> {
+        GalaxyDb::read_journal(galaxy_file_path).map(|(galaxy, _)| galaxy)
+    }
+
+    fn read_journal(galaxy_file_path: &PathBuf) -> Result<(GalaxyDb, usize), Error> {
+        // Read galaxy database and the length of its complete records, empty if there is none yet
+        let saved = match std::fs::read_to_string(galaxy_file_path) {
+            Ok(saved) => saved,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok((GalaxyDb::default(), 0)),
+            Err(err) => return Err(err.into()),
+        };
+
+        // A run interrupted or still busy appending leaves an incomplete last line, which is skipped
+        let complete_len = saved.rfind('\n').map_or(0, |i| i + 1);
+
+        let mut galaxy = GalaxyDb::default();
+        for (i, line) in saved[..complete_len].lines().enumerate() {
+            let record = serde_json::from_str(line).map_err(|err| {
+                Error::LocalState(format!(
+                    "Galaxy database {} is corrupt at line {}: {}. Delete it and run the galaxy command with --sync again.",
+                    galaxy_file_path.display(),
+                    i + 1,
+                    err
+                ))
+            })?;
+            galaxy.apply(record);
+        }
+        Ok((galaxy, complete_len))
+    }
+
+    pub fn repair(galaxy_file_path: &PathBuf) -> Result<(), Error> {
+        // Drop an incomplete last line left by an interrupted run, so new records start on a line
+        let (_, complete_len) = GalaxyDb::read_journal(galaxy_file_path)?;
+        match OpenOptions::new().write(true).open(galaxy_file_path) {
+            Ok(galaxy_file) => Ok(galaxy_file.set_len(complete_len as u64)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn apply(&mut self, record: GalaxyRecord) {
+        match record {
+            GalaxyRecord::Systems {
+                page,
+                total,
+                systems,
+            } => {
+                self.systems_pages_done = self.systems_pages_done.max(page);
+                self.systems_total = total;
+                for system in systems {
+                    self.systems.insert(system.symbol.clone(), system);
+                }
+            }
+            GalaxyRecord::SystemWaypoints {
+                system_symbol,
+                waypoints,
+            } => {
+                for waypoint in waypoints {
+                    self.waypoints.insert(waypoint.symbol.clone(), waypoint);
+                }
+                self.synced_systems.insert(system_symbol);
+            }
+            GalaxyRecord::ScannedWaypoints { waypoints } => {
+                for waypoint in waypoints {
+                    self.scanned_waypoints.insert(waypoint.symbol.clone());
+                    self.waypoints.insert(waypoint.symbol.clone(), waypoint);
+                }
+            }
+        }
+    }
+
+    fn append(&mut self, galaxy_file_path: &PathBuf, record: GalaxyRecord) -> Result<(), Error> {
+        // Write the record as one line, then apply it to the database in memory
+        let line = format!("{}\n", serde_json::to_string(&record).unwrap());
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(galaxy_file_path)?
+            .write_all(line.as_bytes())?;
+        self.apply(record);
+        Ok(())
+    }
+
+    pub fn add_systems_page(
+        &mut self,
+        galaxy_file_path: &PathBuf,
+        page: u32,
+        total: u64,
+        systems: Vec<System>,
+    ) -> Result<(), Error> {
+        self.append(
+            galaxy_file_path,
+            GalaxyRecord::Systems {
+                page,
+                total,
+                systems,
+            },
+        )
+    }
+
+    pub fn add_system_waypoints(
+        &mut self,
+        galaxy_file_path: &PathBuf,
+        system_symbol: &str,
+        waypoints: Vec<Waypoint>,
+    ) -> Result<(), Error> {
+        // Store waypoints of a system and mark the system as synced
+        self.append(
+            galaxy_file_path,
+            GalaxyRecord::SystemWaypoints {
+                system_symbol: system_symbol.to_string(),
+                waypoints,
+            },
+        )
+    }
+
+    pub fn add_scanned_waypoints(
+        &mut self,
+        galaxy_file_path: &PathBuf,
+        waypoints: Vec<Waypoint>,
+    ) -> Result<(), Error> {
+        self.append(
+            galaxy_file_path,
+            GalaxyRecord::ScannedWaypoints { waypoints },
+        )
+    }
+
+    pub fn compact(galaxy_file_path: &PathBuf) -> Result<(), Error> {
+        // Rewrite the journal on disk with one record per system, as scans may have added
+        // records since a sync read it, and replace the file only once complete
+        let (galaxy, compacted_len) = GalaxyDb::read_journal(galaxy_file_path)?;
+        let tmp_file_path = galaxy_file_path.with_extension("tmp");
+        let mut tmp_file = File::create(&tmp_file_path)?;
+        let systems = GalaxyRecord::Systems {
+            page: galaxy.systems_pages_done,
+            total: galaxy.systems_total,
+            systems: galaxy.systems.values().cloned().collect(),
+        };
+        writeln!(tmp_file, "{}", serde_json::to_string(&systems).unwrap())?;
+        for system_symbol in &galaxy.synced_systems {
+            let waypoints = GalaxyRecord::SystemWaypoints {
+                system_symbol: system_symbol.clone(),
+                waypoints: galaxy.system_waypoint_list(system_symbol),
+            };
+            writeln!(tmp_file, "{}", serde_json::to_string(&waypoints).unwrap())?;
+        }
+        let scanned = GalaxyRecord::ScannedWaypoints {
+            waypoints: galaxy
+                .scanned_waypoint_list()
+                .into_iter()
+                .cloned()
+                .collect(),
+        };
+        writeln!(tmp_file, "{}", serde_json::to_string(&scanned).unwrap())?;
+
+        // Carry over complete records appended while the temp file was written
+        let saved = std::fs::read(galaxy_file_path)?;
+        let complete_len = saved.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        if complete_len > compacted_len {
+            tmp_file.write_all(&saved[compacted_len..complete_len])?;
+        }
+        tmp_file.sync_all()?;
+        std::fs::rename(&tmp_file_path, galaxy_file_path)?;
+        Ok(())
+    }
+
+    pub fn systems_complete(&self) -> bool {
+        // All systems are listed once the reported total is reached
+        self.systems_total > 0 && self.systems.len() as u64 >= self.systems_total
+    }
+
+    pub fn unsynced_systems(&self) -> Vec<String> {
+        // Systems whose waypoints still have to be crawled
+        self.systems
+            .keys()
+            .filter(|symbol| !self.synced_systems.contains(*symbol))
+            .cloned()
+            .collect()
+    }
+
+    pub fn waypoint(&self, waypoint_symbol: &str) -> Option<&Waypoint> {
+        self.waypoints.get(waypoint_symbol)
+    }
+
+    pub fn scanned_waypoint_list(&self) -> Vec<&Waypoint> {
+        self.scanned_waypoints
+            .iter()
+            .filter_map(|symbol| self.waypoints.get(symbol))
+            .collect()
+    }
+
+    fn system_waypoint_list(&self, system_symbol: &str) -> Vec<Waypoint> {
+        self.waypoints
+            .values()
+            .filter(|waypoint| waypoint.system_symbol == system_symbol)
+            .cloned()
+            .collect()
+    }
+
+    pub fn system_waypoints(
+        &self,
+        system_symbol: &str,
+        filters: &[(&str, String)],
+        paging: &Paging,
+    ) -> Page<Waypoint> {
+        // Query waypoints of a system like the API does, with type and trait filters
+        let waypoints: Vec<&Waypoint> = self
+            .waypoints
+            .values()
+            .filter(|waypoint| waypoint.system_symbol == system_symbol)
+            .filter(|waypoint| {
+                filters.iter().all(|(key, value)| match *key {
+                    "type" => waypoint.waypoint_type == *value,
+                    _ => waypoint.has_trait(value),
+                })
+            })
+            .collect();
+
+        // Return the requested page shaped like an API response
+        let total = waypoints.len();
+        let (page, limit) = match paging {
+            Paging::Page { page, limit } => (*page as usize, *limit as usize),
+            Paging::All => (1, total.max(1)),
+        };
+        let data: Vec<Waypoint> = waypoints
+            .into_iter()
+            .skip((page - 1) * limit)
+            .take(limit)
+            .cloned()
+            .collect();
+        Page {
+            data,
+            meta: Meta {
+                total: total as u64,
+                page: page as u64,
+                limit: limit as u64,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_galaxy_path(name: &str) -> PathBuf {
+        // Separate file per test and process, as tests run in parallel
+        let path = std::env::temp_dir().join(format!("rst_galaxy_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn system(symbol: &str) -> System {
+        serde_json::from_value(json!({
+            "symbol": symbol,
+            "sectorSymbol": "X1",
+            "type": "RED_STAR",
+            "x": 0,
+            "y": 0
+        }))
+        .unwrap()
+    }
+
+    fn waypoint(symbol: &str, waypoint_type: &str, traits: &[&str]) -> Waypoint {
+        let system_symbol = symbol.rsplit_once('-').unwrap().0;
+        let traits: Vec<_> = traits
+            .iter()
+            .map(|t| json!({ "symbol": t, "name": t }))
+            .collect();
+        serde_json::from_value(json!({
+            "symbol": symbol,
+            "type": waypoint_type,
+            "systemSymbol": system_symbol,
+            "x": 1,
+            "y": 2,
+            "traits": traits
+        }))
+        .unwrap()
+    }
+
+    fn fill(path: &PathBuf) -> GalaxyDb {
+        let mut galaxy = GalaxyDb::read(path).unwrap();
+        galaxy
+            .add_systems_page(path, 1, 2, vec![system("X1-AA"), system("X1-BB")])
+            .unwrap();
+        galaxy
+            .add_system_waypoints(
+                path,
+                "X1-AA",
+                vec![
+                    waypoint("X1-AA-A1", "PLANET", &["MARKETPLACE"]),
+                    waypoint("X1-AA-A2", "MOON", &["SHIPYARD", "MARKETPLACE"]),
+                    waypoint("X1-AA-A3", "PLANET", &[]),
+                ],
+            )
+            .unwrap();
+        galaxy
+            .add_scanned_waypoints(path, vec![waypoint("X1-BB-B1", "ASTEROID", &[])])
+            .unwrap();
+        galaxy
+    }
+
+    fn assert_same(left: &GalaxyDb, right: &GalaxyDb) {
+        assert_eq!(left.systems_pages_done, right.systems_pages_done);
+        assert_eq!(left.systems_total, right.systems_total);
+        assert_eq!(
+            left.systems.keys().collect::<Vec<_>>(),
+            right.systems.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            left.waypoints.keys().collect::<Vec<_>>(),
+            right.waypoints.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(left.synced_systems, right.synced_systems);
+        assert_eq!(left.scanned_waypoints, right.scanned_waypoints);
+    }
+
+    fn waypoint_symbols(page: &Page<Waypoint>) -> Vec<&str> {
+        page.data.iter().map(|w| w.symbol.as_str()).collect()
+    }
+
+    #[test]
+    fn read_returns_appended_records() {
+        let path = temp_galaxy_path("read_returns_appended_records");
+        let galaxy = fill(&path);
+        let saved = GalaxyDb::read(&path).unwrap();
+
+        assert_same(&galaxy, &saved);
+        assert!(saved.systems_complete());
+        assert_eq!(saved.unsynced_systems(), vec!["X1-BB".to_string()]);
+        assert_eq!(saved.scanned_waypoint_list()[0].symbol, "X1-BB-B1");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn read_skips_incomplete_last_line_without_changing_file() {
+        let path = temp_galaxy_path("read_skips_incomplete_last_line");
+        let galaxy = fill(&path);
+        let mut galaxy_file = OpenOptions::new().append(true).open(&path).unwrap();
+        galaxy_file
+            .write_all(b"{\"record\":\"scanned_waypoints\",\"waypo")
+            .unwrap();
+        let len = std::fs::metadata(&path).unwrap().len();
+
+        // Reading leaves the file alone, as a sync may still be appending to it
+        assert_same(&galaxy, &GalaxyDb::read(&path).unwrap());
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), len);
+
+        // Repairing drops the incomplete line, so new records start on their own line
+        GalaxyDb::repair(&path).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().ends_with('\n'));
+        assert_same(&galaxy, &GalaxyDb::read(&path).unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn compact_keeps_all_records() {
+        let path = temp_galaxy_path("compact_keeps_all_records");
+        let mut galaxy = fill(&path);
+
+        // A scan appending through its own copy while a sync runs must survive compaction
+        let mut scan = GalaxyDb::read(&path).unwrap();
+        scan.add_scanned_waypoints(&path, vec![waypoint("X1-BB-B2", "MOON", &[])])
+            .unwrap();
+        galaxy
+            .add_system_waypoints(&path, "X1-BB", vec![waypoint("X1-BB-B1", "ASTEROID", &[])])
+            .unwrap();
+        let before = GalaxyDb::read(&path).unwrap();
+        GalaxyDb::compact(&path).unwrap();
+        let after = GalaxyDb::read(&path).unwrap();
+
+        assert_same(&before, &after);
+        assert!(after.scanned_waypoints.contains("X1-BB-B2"));
+        assert!(after.unsynced_systems().is_empty());
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 4);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn system_waypoints_filters_and_pages() {
+        let path = temp_galaxy_path("system_waypoints_filters_and_pages");
+        let galaxy = fill(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let all = galaxy.system_waypoints("X1-AA", &[], &Paging::All);
+        assert_eq!(waypoint_symbols(&all), ["X1-AA-A1", "X1-AA-A2", "X1-AA-A3"]);
+        assert_eq!((all.meta.total, all.meta.page, all.meta.limit), (3, 1, 3));
+
+        let planets = galaxy.system_waypoints("X1-AA", &[("type", "PLANET".into())], &Paging::All);
+        assert_eq!(waypoint_symbols(&planets), ["X1-AA-A1", "X1-AA-A3"]);
+
+        let filters = [
+            ("traits", "MARKETPLACE".to_string()),
+            ("traits", "SHIPYARD".to_string()),
+        ];
+        let shipyards = galaxy.system_waypoints("X1-AA", &filters, &Paging::All);
+        assert_eq!(waypoint_symbols(&shipyards), ["X1-AA-A2"]);
+
+        let second = galaxy.system_waypoints("X1-AA", &[], &Paging::Page { page: 2, limit: 2 });
+        assert_eq!(waypoint_symbols(&second), ["X1-AA-A3"]);
+        assert_eq!(
+            (second.meta.total, second.meta.page, second.meta.limit),
+            (3, 2, 2)
+        );
+
+        let empty = galaxy.system_waypoints("X1-CC", &[], &Paging::All);
+        assert!(empty.data.is_empty());
+        assert_eq!((empty.meta.total, empty.meta.limit), (0, 1));
+    }
+}
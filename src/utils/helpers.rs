@@ -1,46 +1,147 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::ArgMatches;
 use colored::Colorize;
-use serde_json::Value;
+use std::io::Write;
+use std::time::Duration;
+
+use crate::api::requests::Paging;
+use crate::cli::ALL_COMMANDS;
+use crate::error::Error;
 
 pub fn location_split(location: &str) -> (String, String) {
     // Divide provided location into system and waypoint coords
     // input X1-DF55-20250Z to system: X1-DF55 and waypoint: 20250Z
     let location_split: Vec<&str> = location.split('-').collect();
-    let system = format!(
-        "{}-{}",
-        location_split[0].trim_matches('"'),
-        location_split[1].trim_matches('"')
-    );
-    let waypoint = location.trim_matches('"').to_string();
+    let system = format!("{}-{}", location_split[0], location_split[1]);
+    let waypoint = location.to_string();
 
     (system, waypoint)
 }
 
-pub fn no_token_error() -> Result<(), Box<dyn std::error::Error>> {
-    Err(Box::new(std::io::Error::new(
-        std::io::ErrorKind::Other,
-        "No token found. Please login first.",
-    )))
+pub fn get_paging(sub_matches: &ArgMatches) -> Paging {
+    // Read paging arguments of list commands, defaulting to the first page of 10 items
+    if sub_matches.get_flag(ALL_COMMANDS.arg_all.1) {
+        return Paging::All;
+    }
+    Paging::Page {
+        page: *sub_matches
+            .get_one::<u32>(ALL_COMMANDS.arg_page.1)
+            .unwrap_or(&1),
+        limit: *sub_matches
+            .get_one::<u32>(ALL_COMMANDS.arg_limit.1)
+            .unwrap_or(&10),
+    }
+}
+
+pub fn get_timeout(matches: &ArgMatches) -> Duration {
+    // Read the request timeout of the root command, which has a default value
+    Duration::from_secs(*matches.get_one::<u64>(ALL_COMMANDS.arg_timeout.1).unwrap())
 }
 
-pub fn act_on_req_result(
-    req_result: Result<Value, Box<dyn std::error::Error>>,
-    print_statement: &str,
-    print_data: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    match req_result {
-        Ok(data) => {
-            println!("{}", print_statement.green());
-            if print_data {
-                println!("{:#?}", data["data"]);
-            }
-            Ok(())
+pub fn get_max_attempts(matches: &ArgMatches) -> u32 {
+    // Read the maximum number of request attempts of the root command, which has a default value
+    *matches
+        .get_one::<u32>(ALL_COMMANDS.arg_max_attempts.1)
+        .unwrap()
+}
+
+pub fn is_local_command(matches: &ArgMatches) -> bool {
+    // Commands that only read local files and need no connection to the API
+    match matches.subcommand() {
+        Some(("status", sub_matches)) => sub_matches.get_flag(ALL_COMMANDS.arg_local.1),
+        Some(("location", sub_matches)) => {
+            sub_matches.get_flag(ALL_COMMANDS.arg_offline.1)
+                || sub_matches.get_flag(ALL_COMMANDS.arg_known.1)
         }
-        Err(data) => {
-            let err_msg = data.to_string();
-            Err(Box::new(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                err_msg,
-            )))
+        Some(("ship", sub_matches)) => sub_matches.get_flag(ALL_COMMANDS.arg_surveys.1),
+        Some(("galaxy", sub_matches)) => !sub_matches.get_flag(ALL_COMMANDS.arg_sync.1),
+        _ => false,
+    }
+}
+
+pub fn parse_reset_date(reset_date: &str) -> Option<NaiveDate> {
+    // Server reset dates are plain dates, e.g., 2023-11-18, but accept full timestamps as well
+    NaiveDate::parse_from_str(reset_date, "%Y-%m-%d")
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(reset_date)
+                .ok()
+                .map(|date| date.date_naive())
+        })
+}
+
+pub fn get_waypoint_filters(sub_matches: &ArgMatches) -> Vec<(&'static str, String)> {
+    // Read waypoint type and trait filters as API query parameters
+    let mut filters: Vec<(&str, String)> = Vec::new();
+    if let Some(waypoint_type) = sub_matches.get_one::<String>(ALL_COMMANDS.arg_type.1) {
+        filters.push(("type", waypoint_type.to_uppercase()));
+    }
+    if let Some(traits) = sub_matches.get_many::<String>(ALL_COMMANDS.arg_trait.1) {
+        filters.extend(traits.map(|t| ("traits", t.to_uppercase())));
+    }
+    filters
+}
+
+pub fn parse_units(units: &str) -> Result<u32, Error> {
+    // Parse a unit count passed on the command line
+    units
+        .parse::<u32>()
+        .map_err(|_| Error::Command(format!("Invalid number of units '{}'.", units)))
+}
+
+pub fn user_confirms(question: &str) -> bool {
+    // Ask the user a yes/no question on the command line
+    println!("{} (y/n)", question);
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).unwrap();
+    input.trim() == "y"
+}
+
+pub async fn wait_until(timestamp: &str, reason: &str) -> Result<(), Error> {
+    // Sleep until the given RFC 3339 timestamp, showing a live countdown
+    let target = DateTime::parse_from_rfc3339(timestamp)
+        .map_err(|err| Error::Deserialize {
+            context: format!("Invalid timestamp '{}'", timestamp),
+            message: err.to_string(),
+        })?
+        .with_timezone(&Utc);
+    loop {
+        let remaining = (target - Utc::now()).num_milliseconds();
+        if remaining <= 0 {
+            break;
         }
+        print!(
+            "\rWaiting for {}: {}s remaining...   ",
+            reason,
+            (remaining + 999) / 1000
+        );
+        std::io::stdout().flush()?;
+        let tick = remaining.min(1000) as u64;
+        tokio::time::sleep(Duration::from_millis(tick)).await;
+    }
+    println!(
+        "\r{} {}                    ",
+        "Done waiting for".green(),
+        reason
+    );
+    Ok(())
+}
+
+pub fn no_token_error() -> Result<(), Error> {
+    Err(Error::LocalState(
+        "No token found. Please login first.".to_string(),
+    ))
+}
+
+pub fn act_on_req_result<T: std::fmt::Debug>(
+    req_result: Result<T, Error>,
+    print_statement: &str,
+    print_data: bool,
+) -> Result<(), Error> {
+    let data = req_result?;
+    println!("{}", print_statement.green());
+    if print_data {
+        println!("{:#?}", data);
     }
+    Ok(())
 }
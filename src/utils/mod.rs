@@ -1,2 +1,4 @@
+pub mod display;
+pub mod galaxy;
 pub mod helpers;
 pub mod status;
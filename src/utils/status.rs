@@ -1,8 +1,17 @@
+use chrono::{DateTime, Utc};
 use dirs::home_dir;
 use std::collections::HashMap;
+use std::io::ErrorKind;
 use std::path::PathBuf;
 use std::process;
 
+use crate::api::models::Survey;
+use crate::error::Error;
+
+pub const GAME_FILE_NAME: &str = ".spacetraders";
+pub const SURVEY_FILE_NAME: &str = ".spacetraders_surveys";
+pub const GALAXY_FILE_NAME: &str = ".spacetraders_galaxy";
+
 pub fn set_home_dir_path(file_name: &str) -> PathBuf {
     let mut path = home_dir().unwrap();
     path.push(file_name);
@@ -59,8 +68,49 @@ pub fn reset_local_status(
     callsign: String,
     token: String,
 ) -> &mut HashMap<String, String> {
-    game_status.clear();
+    // Replace the agent only, the saved reset date still belongs to the server
     game_status.insert("callsign".to_string(), callsign);
     game_status.insert("token".to_string(), token);
     game_status
 }
+
+pub fn read_surveys(survey_file_path: &PathBuf) -> Result<Vec<Survey>, Error> {
+    // Read survey file, starting without surveys if there is none yet
+    let saved = match std::fs::read_to_string(survey_file_path) {
+        Ok(saved) => saved,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let surveys: Vec<Survey> = serde_json::from_str(&saved).map_err(|err| {
+        Error::LocalState(format!(
+            "Survey file {} is corrupt: {}. Delete it and survey again.",
+            survey_file_path.display(),
+            err
+        ))
+    })?;
+
+    // Drop all surveys that already expired
+    Ok(surveys.into_iter().filter(survey_valid).collect())
+}
+
+pub fn save_surveys(survey_file_path: &PathBuf, surveys: &[Survey]) -> Result<(), Error> {
+    let survey_string = serde_json::to_string_pretty(surveys).unwrap();
+    std::fs::write(survey_file_path, survey_string)?;
+    Ok(())
+}
+
+pub fn survey_valid(survey: &Survey) -> bool {
+    // A survey is valid as long as its expiration lies in the future
+    DateTime::parse_from_rfc3339(&survey.expiration)
+        .ok()
+        .map(|exp| exp.with_timezone(&Utc) > Utc::now())
+        .unwrap_or(false)
+}
+
+pub fn invalidate_local_state(game_status: &mut HashMap<String, String>) {
+    // Drop the token and all local universe data, which are invalid after a server reset
+    game_status.remove("token");
+    for file_name in [SURVEY_FILE_NAME, GALAXY_FILE_NAME] {
+        let _ = std::fs::remove_file(set_home_dir_path(file_name));
+    }
+}